@@ -0,0 +1,166 @@
+//! Throughput of parsing, writing and querying catalogues.
+//!
+//! Every benchmark runs on `data/film_info.txt` and on a synthetic catalogue with long cast lists
+//! and Unicode names. Run with `cargo bench`, or `cargo bench -- parse` for one group.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use film_parser::dedupe::{self, MergePolicy};
+use film_parser::index::SearchIndex;
+use film_parser::synthetic::SyntheticCatalogue;
+use film_parser::view::{self, GroupBy, SortKey};
+use film_parser::*;
+use pest::Parser;
+use std::hint::black_box;
+use std::path::PathBuf;
+
+/// Films of the synthetic catalogue.
+const SYNTHETIC_FILMS: usize = 5_000;
+
+/// Stars of every synthetic film.
+const SYNTHETIC_CAST: usize = 25;
+
+/// The record lines of both corpora, with their names.
+fn corpora() -> Vec<(&'static str, Vec<String>)> {
+    let sample = read_lines("data/film_info.txt").expect("Failed to read data/film_info.txt");
+    let synthetic = SyntheticCatalogue::new(SYNTHETIC_FILMS)
+        .with_cast(SYNTHETIC_CAST)
+        .record_lines();
+    vec![("film_info", sample), ("synthetic", synthetic)]
+}
+
+fn byte_count(lines: &[String]) -> u64 {
+    lines.iter().map(|line| line.len() as u64 + 1).sum()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("film_parser_bench_{}_{}", std::process::id(), name))
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, lines) in corpora() {
+        group.throughput(Throughput::Bytes(byte_count(&lines)));
+        group.bench_with_input(BenchmarkId::new("grammar", name), &lines, |b, lines| {
+            b.iter(|| {
+                for line in lines {
+                    black_box(FilmParser::parse(Rule::file, line).unwrap());
+                }
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("parse_to_struct", name),
+            &lines,
+            |b, lines| {
+                b.iter(|| {
+                    for line in lines {
+                        let pair = FilmParser::parse(Rule::file, line).unwrap().next().unwrap();
+                        black_box(Film::parse_to_struct(pair).unwrap());
+                    }
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("film_ref", name), &lines, |b, lines| {
+            let options = ParseOptions::strict();
+            b.iter(|| {
+                for line in lines {
+                    black_box(FilmRef::parse(line, &options).unwrap());
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("lenient", name), &lines, |b, lines| {
+            b.iter_batched(
+                || lines.clone(),
+                |lines| parse_film_lines_with(lines, &ParseOptions::lenient()).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+
+        let path = temp_path(&format!("{}.txt", name));
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        group.bench_with_input(BenchmarkId::new("parse_file", name), &path, |b, path| {
+            b.iter(|| sources::parse_file(path, &ParseOptions::strict()).unwrap())
+        });
+        let query = Query::parse("genre = horror and year >= 2000").unwrap();
+        group.bench_with_input(BenchmarkId::new("scan_file", name), &path, |b, path| {
+            b.iter(|| {
+                sources::scan_file(path, &ParseOptions::strict(), |film| {
+                    query.matches_ref(film)
+                })
+                .unwrap()
+            })
+        });
+        std::fs::remove_file(&path).unwrap();
+    }
+    group.finish();
+}
+
+fn write(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write");
+    for (name, lines) in corpora() {
+        let films = parse_film_lines(lines).unwrap();
+        group.throughput(Throughput::Elements(films.len() as u64));
+        group.bench_with_input(BenchmarkId::new("record_line", name), &films, |b, films| {
+            b.iter(|| {
+                for film in films {
+                    black_box(film.to_record_line());
+                }
+            })
+        });
+        for extension in ["txt", "jsonl", "txt.gz", "txt.zst"] {
+            let path = temp_path(&format!("{}.{}", name, extension));
+            let filename = path.to_str().unwrap().to_string();
+            group.bench_with_input(
+                BenchmarkId::new(format!("catalogue_{}", extension), name),
+                &films,
+                |b, films| b.iter(|| write_catalogue(films, &filename).unwrap()),
+            );
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+    group.finish();
+}
+
+fn collection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collection");
+    for (name, lines) in corpora() {
+        let films = parse_film_lines(lines).unwrap();
+        group.throughput(Throughput::Elements(films.len() as u64));
+        let query =
+            Query::parse("(genre = horror or star ~ \"o'connor\") and not year < 1990").unwrap();
+        group.bench_with_input(BenchmarkId::new("query", name), &films, |b, films| {
+            b.iter(|| films.iter().filter(|film| query.matches(film)).count())
+        });
+        let sort_keys = SortKey::parse_list("-year,title").unwrap();
+        group.bench_with_input(BenchmarkId::new("sort", name), &films, |b, films| {
+            b.iter_batched(
+                || films.clone(),
+                |mut films| view::sort_films(&mut films, &sort_keys),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(
+            BenchmarkId::new("group_by_star", name),
+            &films,
+            |b, films| b.iter(|| view::group_films(films, GroupBy::Star).len()),
+        );
+        group.bench_with_input(BenchmarkId::new("stats", name), &films, |b, films| {
+            b.iter(|| CatalogueStats::compute(films))
+        });
+        group.bench_with_input(BenchmarkId::new("index_build", name), &films, |b, films| {
+            b.iter(|| SearchIndex::build(films))
+        });
+        let index = SearchIndex::build(&films);
+        group.bench_with_input(
+            BenchmarkId::new("index_search", name),
+            &index,
+            |b, index| b.iter(|| index.search("night* \"village where\"", 10)),
+        );
+        group.bench_with_input(BenchmarkId::new("dedupe", name), &films, |b, films| {
+            b.iter(|| dedupe::dedupe(films, MergePolicy::default()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, write, collection);
+criterion_main!(benches);
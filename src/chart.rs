@@ -0,0 +1,139 @@
+//! Text-mode bar charts and histograms for catalogue statistics, drawn with Unicode block characters.
+
+use crate::stats::Count;
+use colored::*;
+
+/// Width used when the terminal width is unknown.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Smallest width a chart is drawn with.
+pub const MIN_WIDTH: usize = 20;
+
+/// Default number of rows of a histogram.
+pub const DEFAULT_HEIGHT: usize = 8;
+
+/// Horizontal blocks from one eighth to a full cell.
+const HORIZONTAL_BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Vertical blocks from one eighth to a full cell.
+const VERTICAL_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Returns the width of the terminal standard output is written to. When standard output is not
+/// a terminal, e.g. when it is piped, the width is read from the `COLUMNS` environment variable,
+/// or is `DEFAULT_WIDTH`.
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| usize::from(width))
+        .filter(|&width| width > 0)
+        .or_else(|| {
+            std::env::var("COLUMNS")
+                .ok()
+                .and_then(|columns| columns.trim().parse::<usize>().ok())
+        })
+        .unwrap_or(DEFAULT_WIDTH)
+        .max(MIN_WIDTH)
+}
+
+/// Draws a horizontal bar of `value` scaled so that `max` fills `width` cells.
+pub fn bar(value: usize, max: usize, width: usize) -> String {
+    if max == 0 || width == 0 {
+        return String::new();
+    }
+    let eighths = value * width * 8 / max;
+    let mut bar = HORIZONTAL_BLOCKS[7].to_string().repeat(eighths / 8);
+    let partial = eighths % 8;
+    if partial > 0 {
+        bar.push(HORIZONTAL_BLOCKS[partial - 1]);
+    }
+    if bar.is_empty() && value > 0 {
+        bar.push(HORIZONTAL_BLOCKS[0]);
+    }
+    bar
+}
+
+/// Renders a horizontal bar chart with one labelled bar per row, fitted into `width` columns.
+/// Labels longer than a third of the width are shortened.
+pub fn render_bar_chart(title: &str, rows: &[Count], width: usize) -> String {
+    let width = width.max(MIN_WIDTH);
+    let max = rows.iter().map(|row| row.count).max().unwrap_or(0);
+    let count_width = max.to_string().len();
+    let label_width = rows
+        .iter()
+        .map(|row| row.key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(width / 3);
+    let bar_width = width.saturating_sub(label_width + count_width + 3).max(1);
+
+    let mut chart = format!("{}\n", title.green().bold());
+    for row in rows {
+        let label = truncate(&row.key, label_width);
+        let padding = " ".repeat(label_width - label.chars().count());
+        chart.push_str(&format!(
+            "{}{} {} {}\n",
+            label.italic(),
+            padding,
+            bar(row.count, max, bar_width).yellow(),
+            row.count
+        ));
+    }
+    chart
+}
+
+/// Renders a vertical histogram with one column per row, `height` lines tall.
+/// Falls back to a bar chart when the columns do not fit into `width`.
+pub fn render_histogram(title: &str, rows: &[Count], height: usize, width: usize) -> String {
+    let column_width = rows
+        .iter()
+        .map(|row| row.key.chars().count().max(row.count.to_string().len()))
+        .max()
+        .unwrap_or(1)
+        + 1;
+    if rows.is_empty() || column_width * rows.len() > width.max(MIN_WIDTH) {
+        return render_bar_chart(title, rows, width);
+    }
+
+    let height = height.max(1);
+    let max = rows.iter().map(|row| row.count).max().unwrap_or(0).max(1);
+    let levels: Vec<usize> = rows
+        .iter()
+        .map(|row| row.count * height * 8 / max)
+        .collect();
+
+    let mut chart = format!("{}\n", title.green().bold());
+    for line in (0..height).rev() {
+        let mut text = String::new();
+        for level in &levels {
+            let cell = match level.saturating_sub(line * 8) {
+                0 => ' ',
+                filled if filled >= 8 => VERTICAL_BLOCKS[7],
+                filled => VERTICAL_BLOCKS[filled - 1],
+            };
+            text.push_str(&cell.to_string().repeat(column_width - 1));
+            text.push(' ');
+        }
+        chart.push_str(&format!("{}\n", text.trim_end().yellow()));
+    }
+    for values in [
+        rows.iter()
+            .map(|row| row.count.to_string())
+            .collect::<Vec<_>>(),
+        rows.iter().map(|row| row.key.clone()).collect::<Vec<_>>(),
+    ] {
+        let line: String = values
+            .iter()
+            .map(|value| format!("{:<width$}", value, width = column_width))
+            .collect();
+        chart.push_str(&format!("{}\n", line.trim_end().italic()));
+    }
+    chart
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut shortened: String = text.chars().take(width.saturating_sub(1)).collect();
+    shortened.push('…');
+    shortened
+}
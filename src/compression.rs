@@ -0,0 +1,157 @@
+//! Transparent gzip and zstd compression of catalogue files.
+//!
+//! Compressed input is recognised by its magic bytes, whatever the file is called, and is
+//! decompressed before it is decoded. Output is compressed when the file name ends with `.gz` or
+//! `.zst`, so `films.txt.gz` holds gzip-compressed record lines and `out.jsonl.zst` holds
+//! zstd-compressed JSON lines.
+
+use crate::FilmParserError;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::borrow::Cow;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Magic bytes at the start of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression level of zstd output; 0 selects the library default.
+const ZSTD_LEVEL: i32 = 0;
+
+/// How a file is compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Not compressed.
+    None,
+    /// gzip, extension `.gz`.
+    Gzip,
+    /// zstd, extension `.zst`.
+    Zstd,
+}
+
+impl Compression {
+    /// Returns the compression selected by the extension of the path.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Returns the compression of the bytes, recognised by their magic bytes.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            Compression::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    /// Decompresses the bytes.
+    pub fn decompress(self, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+        let mut decompressed = Vec::new();
+        match self {
+            Compression::None => return Ok(bytes),
+            Compression::Gzip => {
+                MultiGzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+            }
+            Compression::Zstd => {
+                zstd::stream::read::Decoder::new(bytes.as_slice())?
+                    .read_to_end(&mut decompressed)?;
+            }
+        }
+        Ok(decompressed)
+    }
+}
+
+/// Returns the path without a `.gz` or `.zst` extension, keeping its directory, e.g.
+/// `data/out.jsonl` for `data/out.jsonl.zst`.
+pub fn uncompressed_path(path: &Path) -> Cow<'_, Path> {
+    match Compression::from_path(path) {
+        Compression::None => Cow::Borrowed(path),
+        _ => Cow::Owned(path.with_extension("")),
+    }
+}
+
+/// Reads a file, decompressing it if it starts with gzip or zstd magic bytes.
+pub fn read_file(path: &Path) -> Result<Vec<u8>, FilmParserError> {
+    let reading_error =
+        |e: io::Error| FilmParserError::FileReadingError(format!("{}: {}", path.display(), e));
+    let bytes = fs::read(path).map_err(reading_error)?;
+    Compression::detect(&bytes)
+        .decompress(bytes)
+        .map_err(reading_error)
+}
+
+/// A file writer that compresses according to the extension of the file.
+/// `finish` must be called to complete the compressed stream.
+pub struct FileWriter {
+    inner: Inner,
+}
+
+enum Inner {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::stream::write::Encoder<'static, BufWriter<File>>),
+}
+
+impl FileWriter {
+    /// Creates the file, compressing what is written if it ends with `.gz` or `.zst`.
+    pub fn create(path: &Path) -> Result<Self, FilmParserError> {
+        let file = File::create(path)
+            .map_err(|_| FilmParserError::FileCreatingError(path.display().to_string()))?;
+        let file = BufWriter::new(file);
+        let inner = match Compression::from_path(path) {
+            Compression::None => Inner::Plain(file),
+            Compression::Gzip => Inner::Gzip(GzEncoder::new(file, flate2::Compression::default())),
+            Compression::Zstd => Inner::Zstd(
+                zstd::stream::write::Encoder::new(file, ZSTD_LEVEL)
+                    .map_err(|_| FilmParserError::FileCreatingError(path.display().to_string()))?,
+            ),
+        };
+        Ok(FileWriter { inner })
+    }
+
+    /// Completes the compressed stream and flushes the file.
+    pub fn finish(self) -> io::Result<()> {
+        match self.inner {
+            Inner::Plain(mut file) => file.flush(),
+            Inner::Gzip(encoder) => encoder.finish()?.flush(),
+            Inner::Zstd(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for FileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            Inner::Plain(file) => file.write(buf),
+            Inner::Gzip(encoder) => encoder.write(buf),
+            Inner::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            Inner::Plain(file) => file.flush(),
+            Inner::Gzip(encoder) => encoder.flush(),
+            Inner::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Writes the bytes to a file, compressing them if it ends with `.gz` or `.zst`.
+pub fn write_file(path: &Path, bytes: &[u8]) -> Result<(), FilmParserError> {
+    let mut writer = FileWriter::create(path)?;
+    writer
+        .write_all(bytes)
+        .and_then(|_| writer.finish())
+        .map_err(|_| FilmParserError::FileWritingError(path.display().to_string()))
+}
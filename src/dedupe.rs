@@ -0,0 +1,243 @@
+//! Detection and merging of duplicate film records.
+//!
+//! Two records are likely duplicates when their normalised title, year and director are equal.
+//! Normalising lowercases the text, drops punctuation and collapses whitespace, so
+//! `The  Witch` and `the witch.` fall into the same group. Merged films keep a record of which
+//! input record every field was taken from.
+
+use crate::{Film, FilmField, FilmParserError};
+use std::collections::HashMap;
+
+/// How list fields (genres and stars) of duplicates are merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListMerge {
+    /// Every distinct item of every record, in order of appearance.
+    Union,
+    /// The list of the first record.
+    First,
+}
+
+/// How the descriptions of duplicates are merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptionMerge {
+    /// The longest description.
+    Longest,
+    /// The description of the first record.
+    First,
+}
+
+/// How a group of duplicates is merged into one film. Fields not covered by the policy
+/// are always taken from the first record of the group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergePolicy {
+    /// How genres and stars are merged.
+    pub lists: ListMerge,
+    /// How descriptions are merged.
+    pub description: DescriptionMerge,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        MergePolicy {
+            lists: ListMerge::Union,
+            description: DescriptionMerge::Longest,
+        }
+    }
+}
+
+impl MergePolicy {
+    /// Parses a comma-separated policy such as `union,longest` or `first`.
+    /// `union` merges lists, `longest` prefers the longest description and `first`
+    /// takes everything not named otherwise from the first record.
+    pub fn parse_list(list: &str) -> Result<Self, FilmParserError> {
+        let mut policy = MergePolicy {
+            lists: ListMerge::First,
+            description: DescriptionMerge::First,
+        };
+        for name in list
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match name.to_lowercase().as_str() {
+                "union" => policy.lists = ListMerge::Union,
+                "longest" => policy.description = DescriptionMerge::Longest,
+                "first" => {}
+                _ => {
+                    return Err(FilmParserError::InvalidOption(format!(
+                        "policy {}, expected union, longest or first",
+                        name
+                    )))
+                }
+            }
+        }
+        Ok(policy)
+    }
+}
+
+/// Records that are likely the same film.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// The normalised title, year and director shared by the records.
+    pub key: String,
+    /// Positions of the records in the input, in input order.
+    pub records: Vec<usize>,
+}
+
+/// Where the value of one field of a merged film came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSource {
+    /// The field.
+    pub field: FilmField,
+    /// Positions of the input records that contributed to the value.
+    pub records: Vec<usize>,
+}
+
+/// A film merged from a group of duplicates, with the provenance of its fields.
+#[derive(Debug, Clone)]
+pub struct MergedFilm {
+    /// The merged film.
+    pub film: Film,
+    /// Positions of the merged records in the input.
+    pub records: Vec<usize>,
+    /// The records every field was taken from, in `FilmField::ALL` order.
+    pub provenance: Vec<FieldSource>,
+}
+
+/// A catalogue with its duplicates merged.
+#[derive(Debug, Clone)]
+pub struct Deduplicated {
+    /// The films without duplicates, each in the position of its first record.
+    pub films: Vec<Film>,
+    /// The films that were merged from several records.
+    pub merged: Vec<MergedFilm>,
+}
+
+/// Returns the key records are grouped by: normalised title, year and director.
+pub fn duplicate_key(film: &Film) -> String {
+    format!(
+        "{}|{}|{}",
+        normalize(&film.title),
+        film.year,
+        normalize(&film.director)
+    )
+}
+
+/// Finds the groups of two or more records with the same key, ordered by their first record.
+pub fn find_duplicates(films: &[Film]) -> Vec<DuplicateGroup> {
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (record, film) in films.iter().enumerate() {
+        let key = duplicate_key(film);
+        match positions.get(&key) {
+            Some(&group) => groups[group].records.push(record),
+            None => {
+                positions.insert(key.clone(), groups.len());
+                groups.push(DuplicateGroup {
+                    key,
+                    records: vec![record],
+                });
+            }
+        }
+    }
+    groups.retain(|group| group.records.len() > 1);
+    groups
+}
+
+/// Merges the records of a group into one film.
+pub fn merge_group(films: &[Film], group: &DuplicateGroup, policy: MergePolicy) -> MergedFilm {
+    let first = group.records[0];
+    let mut film = films[first].clone();
+    let mut provenance: Vec<FieldSource> = FilmField::ALL
+        .iter()
+        .map(|&field| FieldSource {
+            field,
+            records: vec![first],
+        })
+        .collect();
+
+    if policy.lists == ListMerge::Union {
+        for &record in &group.records[1..] {
+            let other = &films[record];
+            if union_into(&mut film.genre, &other.genre) {
+                source_of(&mut provenance, FilmField::Genre).push(record);
+            }
+            if union_into(&mut film.stars, &other.stars) {
+                source_of(&mut provenance, FilmField::Stars).push(record);
+            }
+        }
+    }
+
+    if policy.description == DescriptionMerge::Longest {
+        for &record in &group.records[1..] {
+            let description = &films[record].description;
+            if description.trim().chars().count() > film.description.trim().chars().count() {
+                film.description = description.clone();
+                *source_of(&mut provenance, FilmField::Description) = vec![record];
+            }
+        }
+    }
+
+    MergedFilm {
+        film,
+        records: group.records.clone(),
+        provenance,
+    }
+}
+
+/// Merges every group of duplicates.
+pub fn dedupe(films: &[Film], policy: MergePolicy) -> Deduplicated {
+    let groups = find_duplicates(films);
+    let mut replaced: HashMap<usize, usize> = HashMap::new();
+    let mut merged = Vec::with_capacity(groups.len());
+    for group in &groups {
+        for &record in &group.records {
+            replaced.insert(record, merged.len());
+        }
+        merged.push(merge_group(films, group, policy));
+    }
+
+    let films = films
+        .iter()
+        .enumerate()
+        .filter_map(|(record, film)| match replaced.get(&record) {
+            None => Some(film.clone()),
+            Some(&group) if merged[group].records[0] == record => Some(merged[group].film.clone()),
+            Some(_) => None,
+        })
+        .collect();
+    Deduplicated { films, merged }
+}
+
+/// Appends the items of `other` missing from `list`, compared ignoring case.
+/// Returns `true` if anything was added.
+fn union_into(list: &mut Vec<String>, other: &[String]) -> bool {
+    let mut added = false;
+    for item in other {
+        let key = normalize(item);
+        if !key.is_empty() && !list.iter().any(|existing| normalize(existing) == key) {
+            list.push(item.clone());
+            added = true;
+        }
+    }
+    added
+}
+
+fn source_of(provenance: &mut [FieldSource], field: FilmField) -> &mut Vec<usize> {
+    &mut provenance
+        .iter_mut()
+        .find(|source| source.field == field)
+        .expect("provenance covers every field")
+        .records
+}
+
+/// Lowercases the text, drops punctuation and collapses whitespace.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
@@ -0,0 +1,177 @@
+//! Semantic comparison of two catalogues.
+//!
+//! Films are matched by identity, the title and year ignoring case and surrounding whitespace,
+//! rather than by line. Matched films are compared field by field: list fields report the items
+//! added and removed, other fields report the old and the new value.
+
+use crate::{Film, FilmField, FilmParserError, SourceLocation};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+/// Title and year of a film.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FilmIdentity {
+    /// The film title.
+    pub title: String,
+    /// The release year.
+    pub year: u32,
+    /// Where the film was read from, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceLocation>,
+}
+
+impl FilmIdentity {
+    fn of(film: &Film) -> Self {
+        FilmIdentity {
+            title: film.title.trim().to_string(),
+            year: film.year,
+            source: film.source.clone(),
+        }
+    }
+}
+
+/// A difference in one field of a film.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldChange {
+    /// A single-valued field changed.
+    Value {
+        /// The field.
+        field: FilmField,
+        /// The value in the old catalogue.
+        old: String,
+        /// The value in the new catalogue.
+        new: String,
+    },
+    /// Items were added to or removed from a list field.
+    List {
+        /// The field.
+        field: FilmField,
+        /// Items only in the new catalogue.
+        added: Vec<String>,
+        /// Items only in the old catalogue.
+        removed: Vec<String>,
+    },
+}
+
+/// A film present in both catalogues with different fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FilmDiff {
+    /// The film, as named in the new catalogue.
+    #[serde(flatten)]
+    pub identity: FilmIdentity,
+    /// Where the film was read from in the old catalogue, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_source: Option<SourceLocation>,
+    /// The changed fields, in `FilmField::ALL` order.
+    pub changes: Vec<FieldChange>,
+}
+
+/// Differences between an old and a new catalogue.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CatalogueDiff {
+    /// Films only in the new catalogue, in its order.
+    pub added: Vec<FilmIdentity>,
+    /// Films only in the old catalogue, in its order.
+    pub removed: Vec<FilmIdentity>,
+    /// Films in both catalogues whose fields differ, in the order of the new catalogue.
+    pub changed: Vec<FilmDiff>,
+}
+
+impl CatalogueDiff {
+    /// Compares two catalogues. When several films share an identity, they are
+    /// matched in the order they appear.
+    pub fn compute(old: &[Film], new: &[Film]) -> Self {
+        let mut unmatched: HashMap<(String, u32), VecDeque<usize>> = HashMap::new();
+        for (index, film) in old.iter().enumerate() {
+            unmatched
+                .entry(identity_key(film))
+                .or_default()
+                .push_back(index);
+        }
+
+        let mut diff = CatalogueDiff::default();
+        let mut matched = vec![false; old.len()];
+        for film in new {
+            match unmatched
+                .get_mut(&identity_key(film))
+                .and_then(VecDeque::pop_front)
+            {
+                Some(index) => {
+                    matched[index] = true;
+                    let changes = compare_films(&old[index], film);
+                    if !changes.is_empty() {
+                        diff.changed.push(FilmDiff {
+                            identity: FilmIdentity::of(film),
+                            old_source: old[index].source.clone(),
+                            changes,
+                        });
+                    }
+                }
+                None => diff.added.push(FilmIdentity::of(film)),
+            }
+        }
+        diff.removed = old
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(film, _)| FilmIdentity::of(film))
+            .collect();
+        diff
+    }
+
+    /// Returns `true` if the catalogues hold the same films.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Serializes the differences as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, FilmParserError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| FilmParserError::SerializationError(e.to_string()))
+    }
+}
+
+/// Compares the fields of two films other than their identity.
+pub fn compare_films(old: &Film, new: &Film) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    for field in FilmField::ALL {
+        let old_values = field.values(old);
+        let new_values = field.values(new);
+        if field.is_list() {
+            let added = missing_from(&new_values, &old_values);
+            let removed = missing_from(&old_values, &new_values);
+            if !added.is_empty() || !removed.is_empty() {
+                changes.push(FieldChange::List {
+                    field,
+                    added,
+                    removed,
+                });
+            }
+        } else {
+            let old_value = old_values.join(", ");
+            let new_value = new_values.join(", ");
+            if old_value.trim() != new_value.trim() {
+                changes.push(FieldChange::Value {
+                    field,
+                    old: old_value,
+                    new: new_value,
+                });
+            }
+        }
+    }
+    changes
+}
+
+fn identity_key(film: &Film) -> (String, u32) {
+    (film.title.trim().to_lowercase(), film.year)
+}
+
+/// Items of `values` that are not in `other`, compared after trimming.
+fn missing_from(values: &[String], other: &[String]) -> Vec<String> {
+    values
+        .iter()
+        .filter(|value| !other.iter().any(|item| item.trim() == value.trim()))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,61 @@
+//! Decoding of catalogue files.
+//!
+//! A file starting with a byte order mark is decoded as UTF-8, UTF-16LE or UTF-16BE according to
+//! the mark, and the mark is dropped. Other files are decoded as UTF-8 unless an encoding is given,
+//! e.g. `windows-1251` or `latin1` for legacy exports; any label known to the
+//! [Encoding Standard](https://encoding.spec.whatwg.org/#names-and-labels) is accepted.
+//!
+//! Offsets such as `SourceLocation::byte_span` refer to the decoded UTF-8 text, without the mark,
+//! not to the bytes of the file.
+//! Files are decompressed before they are decoded, see `compression`.
+
+use crate::compression;
+use crate::FilmParserError;
+use encoding_rs::{Encoding, UTF_8};
+use std::path::Path;
+
+/// Returns the encoding with the given label, e.g. `windows-1251`, `latin1` or `utf-16le`.
+pub fn encoding_for_label(label: &str) -> Result<&'static Encoding, FilmParserError> {
+    Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| FilmParserError::InvalidOption(format!("encoding {}", label)))
+}
+
+/// Decodes the bytes of a file. A byte order mark takes precedence over `encoding`, and text
+/// without a mark is UTF-8 when `encoding` is `None`. Malformed text is an error.
+pub fn decode(
+    bytes: &[u8],
+    encoding: Option<&'static Encoding>,
+) -> Result<String, FilmParserError> {
+    let (encoding, bom_length) = match Encoding::for_bom(bytes) {
+        Some((encoding, bom_length)) => (encoding, bom_length),
+        None => (encoding.unwrap_or(UTF_8), 0),
+    };
+    let (text, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+    if had_errors {
+        let hint = if encoding == UTF_8 {
+            ", use --encoding to read legacy files"
+        } else {
+            ""
+        };
+        return Err(FilmParserError::FileReadingError(format!(
+            "the text is not valid {}{}",
+            encoding.name(),
+            hint
+        )));
+    }
+    Ok(text.into_owned())
+}
+
+/// Reads, decompresses and decodes a file, see `decode`.
+pub fn read_file(
+    path: &Path,
+    encoding: Option<&'static Encoding>,
+) -> Result<String, FilmParserError> {
+    let bytes = compression::read_file(path)?;
+    decode(&bytes, encoding).map_err(|e| match e {
+        FilmParserError::FileReadingError(message) => {
+            FilmParserError::FileReadingError(format!("{}: {}", path.display(), message))
+        }
+        e => e,
+    })
+}
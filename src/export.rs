@@ -0,0 +1,334 @@
+//! Graphviz DOT and GraphML export of the collaboration graph.
+//!
+//! With film nodes selected, the export contains people, films and the credits between them.
+//! Without film nodes, people are connected directly, with edges weighted by the number of
+//! films they share.
+
+use crate::graph::{CollaborationGraph, NodeKind, Role};
+use crate::FilmParserError;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Kind of node included in an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeType {
+    /// People credited as director.
+    Director,
+    /// People credited as writer.
+    Writer,
+    /// People credited as star.
+    Star,
+    /// Films.
+    Film,
+}
+
+impl NodeType {
+    /// Parses a comma-separated list of node types, e.g. `director,star`.
+    pub fn parse_list(input: &str) -> Result<Vec<NodeType>, FilmParserError> {
+        input
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+
+    fn role(self) -> Option<Role> {
+        match self {
+            NodeType::Director => Some(Role::Director),
+            NodeType::Writer => Some(Role::Writer),
+            NodeType::Star => Some(Role::Star),
+            NodeType::Film => None,
+        }
+    }
+}
+
+impl FromStr for NodeType {
+    type Err = FilmParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "film" | "films" => Ok(NodeType::Film),
+            role => match role.parse::<Role>() {
+                Ok(Role::Director) => Ok(NodeType::Director),
+                Ok(Role::Writer) => Ok(NodeType::Writer),
+                Ok(Role::Star) => Ok(NodeType::Star),
+                Err(_) => Err(FilmParserError::InvalidOption(format!(
+                    "node type {}, expected director, writer, star or film",
+                    s
+                ))),
+            },
+        }
+    }
+}
+
+/// Export format of the collaboration graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Graphviz DOT, for rendering with `dot`.
+    Dot,
+    /// GraphML, for Gephi and other graph tools.
+    GraphMl,
+}
+
+impl FromStr for ExportFormat {
+    type Err = FilmParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "dot" | "gv" => Ok(ExportFormat::Dot),
+            "graphml" => Ok(ExportFormat::GraphMl),
+            _ => Err(FilmParserError::InvalidOption(format!(
+                "export format {}, expected dot or graphml",
+                s
+            ))),
+        }
+    }
+}
+
+/// Filters applied when exporting the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Node types to include. People are included when they have a credit in a selected role.
+    pub node_types: Vec<NodeType>,
+    /// Minimum number of shared films for an edge between two people. When films are exported,
+    /// people credited on fewer films are left out.
+    pub min_shared_films: usize,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        ExportOptions {
+            node_types: vec![
+                NodeType::Director,
+                NodeType::Writer,
+                NodeType::Star,
+                NodeType::Film,
+            ],
+            min_shared_films: 1,
+        }
+    }
+}
+
+struct ExportNode {
+    id: String,
+    label: String,
+    kind: NodeKind,
+    roles: Vec<Role>,
+}
+
+struct ExportEdge {
+    source: String,
+    target: String,
+    label: String,
+    weight: usize,
+}
+
+/// Exports the graph in the given format.
+pub fn export(graph: &CollaborationGraph, format: ExportFormat, options: &ExportOptions) -> String {
+    match format {
+        ExportFormat::Dot => to_dot(graph, options),
+        ExportFormat::GraphMl => to_graphml(graph, options),
+    }
+}
+
+/// Exports the graph as a Graphviz DOT graph.
+pub fn to_dot(graph: &CollaborationGraph, options: &ExportOptions) -> String {
+    let (nodes, edges) = select(graph, options);
+    let mut dot = String::from("graph collaborations {\n");
+    for node in &nodes {
+        let shape = match node.kind {
+            NodeKind::Person => "ellipse",
+            NodeKind::Film => "box",
+        };
+        let _ = writeln!(
+            dot,
+            "  {} [label=\"{}\", shape={}, type=\"{}\"];",
+            node.id,
+            escape_dot(&node.label),
+            shape,
+            node_type_label(node)
+        );
+    }
+    for edge in &edges {
+        let _ = writeln!(
+            dot,
+            "  {} -- {} [label=\"{}\", weight={}];",
+            edge.source,
+            edge.target,
+            escape_dot(&edge.label),
+            edge.weight
+        );
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Exports the graph as a GraphML document.
+pub fn to_graphml(graph: &CollaborationGraph, options: &ExportOptions) -> String {
+    let (nodes, edges) = select(graph, options);
+    let mut xml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        "  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n",
+        "  <key id=\"role\" for=\"edge\" attr.name=\"role\" attr.type=\"string\"/>\n",
+        "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n",
+        "  <graph id=\"collaborations\" edgedefault=\"undirected\">\n",
+    ));
+    for node in &nodes {
+        let _ = writeln!(
+            xml,
+            "    <node id=\"{}\"><data key=\"label\">{}</data><data key=\"type\">{}</data></node>",
+            node.id,
+            escape_xml(&node.label),
+            node_type_label(node)
+        );
+    }
+    for (index, edge) in edges.iter().enumerate() {
+        let _ = writeln!(
+            xml,
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\"><data key=\"role\">{}</data><data key=\"weight\">{}</data></edge>",
+            index,
+            edge.source,
+            edge.target,
+            escape_xml(&edge.label),
+            edge.weight
+        );
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+fn node_type_label(node: &ExportNode) -> String {
+    match node.kind {
+        NodeKind::Film => "film".to_string(),
+        NodeKind::Person => node
+            .roles
+            .iter()
+            .map(|role| role.name())
+            .collect::<Vec<_>>()
+            .join(","),
+    }
+}
+
+/// Picks the nodes and edges matching the options.
+fn select(
+    graph: &CollaborationGraph,
+    options: &ExportOptions,
+) -> (Vec<ExportNode>, Vec<ExportEdge>) {
+    let roles: Vec<Role> = options
+        .node_types
+        .iter()
+        .filter_map(|node_type| node_type.role())
+        .collect();
+    let include_films = options.node_types.contains(&NodeType::Film);
+    let min_shared_films = options.min_shared_films.max(1);
+
+    // Selected roles of every person node, in role order.
+    let mut person_roles: BTreeMap<usize, Vec<Role>> = BTreeMap::new();
+    for credit in graph.credits() {
+        if roles.contains(&credit.role) {
+            let person_roles = person_roles.entry(credit.person).or_default();
+            if !person_roles.contains(&credit.role) {
+                person_roles.push(credit.role);
+                person_roles.sort();
+            }
+        }
+    }
+
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let person_node = |person: usize, roles: &Vec<Role>| ExportNode {
+        id: format!("p{}", person),
+        label: graph.nodes()[person].name.clone(),
+        kind: NodeKind::Person,
+        roles: roles.clone(),
+    };
+
+    if include_films {
+        person_roles.retain(|&person, _| graph.films_of(person).len() >= min_shared_films);
+        let mut films: BTreeSet<usize> = BTreeSet::new();
+        for credit in graph.credits() {
+            if person_roles.contains_key(&credit.person) && roles.contains(&credit.role) {
+                films.insert(credit.film);
+                edges.push(ExportEdge {
+                    source: format!("p{}", credit.person),
+                    target: format!("f{}", credit.film),
+                    label: credit.role.name().to_string(),
+                    weight: 1,
+                });
+            }
+        }
+        if roles.is_empty() {
+            films = graph
+                .nodes()
+                .iter()
+                .enumerate()
+                .filter(|(_, node)| node.kind == NodeKind::Film)
+                .map(|(index, _)| index)
+                .collect();
+        }
+        nodes.extend(person_roles.iter().map(|(&p, r)| person_node(p, r)));
+        nodes.extend(films.iter().map(|&film| ExportNode {
+            id: format!("f{}", film),
+            label: graph.nodes()[film].name.clone(),
+            kind: NodeKind::Film,
+            roles: Vec::new(),
+        }));
+        return (nodes, edges);
+    }
+
+    // People only: connect people who share films through credits in the selected roles.
+    let mut shared: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for (index, node) in graph.nodes().iter().enumerate() {
+        if node.kind != NodeKind::Film {
+            continue;
+        }
+        let mut people: Vec<usize> = graph
+            .credits_of(index)
+            .filter(|credit| roles.contains(&credit.role))
+            .map(|credit| credit.person)
+            .collect();
+        people.sort_unstable();
+        people.dedup();
+        for (position, &a) in people.iter().enumerate() {
+            for &b in &people[position + 1..] {
+                *shared.entry((a, b)).or_insert(0) += 1;
+            }
+        }
+    }
+    shared.retain(|_, count| *count >= min_shared_films);
+
+    let mut connected: BTreeSet<usize> = BTreeSet::new();
+    for (&(a, b), &count) in &shared {
+        connected.insert(a);
+        connected.insert(b);
+        edges.push(ExportEdge {
+            source: format!("p{}", a),
+            target: format!("p{}", b),
+            label: format!("{} shared films", count),
+            weight: count,
+        });
+    }
+    nodes.extend(
+        person_roles
+            .iter()
+            .filter(|(person, _)| min_shared_films <= 1 || connected.contains(*person))
+            .map(|(&p, r)| person_node(p, r)),
+    );
+    (nodes, edges)
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,114 @@
+//! Names of the `Film` fields as they are used by queries and command-line options.
+
+use crate::{Film, FilmParserError};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the fields of a `Film` record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilmField {
+    /// `Film::title`
+    Title,
+    /// `Film::year`
+    Year,
+    /// `Film::director`
+    Director,
+    /// `Film::writer`
+    Writer,
+    /// `Film::genre`
+    Genre,
+    /// `Film::stars`
+    Stars,
+    /// `Film::description`
+    Description,
+}
+
+impl FilmField {
+    /// All fields in the order they appear in a source record.
+    pub const ALL: [FilmField; 7] = [
+        FilmField::Title,
+        FilmField::Year,
+        FilmField::Director,
+        FilmField::Writer,
+        FilmField::Genre,
+        FilmField::Stars,
+        FilmField::Description,
+    ];
+
+    /// Returns the lowercase name of the field.
+    pub fn name(self) -> &'static str {
+        match self {
+            FilmField::Title => "title",
+            FilmField::Year => "year",
+            FilmField::Director => "director",
+            FilmField::Writer => "writer",
+            FilmField::Genre => "genre",
+            FilmField::Stars => "stars",
+            FilmField::Description => "description",
+        }
+    }
+
+    /// Returns the key used for the field in source files, e.g. `Title`.
+    pub fn key(self) -> &'static str {
+        match self {
+            FilmField::Title => "Title",
+            FilmField::Year => "Year",
+            FilmField::Director => "Director",
+            FilmField::Writer => "Writer",
+            FilmField::Genre => "Genre",
+            FilmField::Stars => "Stars",
+            FilmField::Description => "Description",
+        }
+    }
+
+    /// Returns `true` for fields holding a list of values (`genre` and `stars`).
+    pub fn is_list(self) -> bool {
+        matches!(self, FilmField::Genre | FilmField::Stars)
+    }
+
+    /// Returns the text values of the field for the given film.
+    /// Single-valued fields yield one value, the year is formatted as a number.
+    pub fn values(self, film: &Film) -> Vec<String> {
+        match self {
+            FilmField::Title => vec![film.title.clone()],
+            FilmField::Year => vec![film.year.to_string()],
+            FilmField::Director => vec![film.director.clone()],
+            FilmField::Writer => vec![film.writer.clone()],
+            FilmField::Genre => film.genre.clone(),
+            FilmField::Stars => film.stars.clone(),
+            FilmField::Description => vec![film.description.clone()],
+        }
+    }
+}
+
+impl fmt::Display for FilmField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Serialize for FilmField {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl FromStr for FilmField {
+    type Err = FilmParserError;
+
+    /// Parses a field name case-insensitively. Singular and plural forms of the
+    /// list fields are both accepted (`genre`/`genres`, `star`/`stars`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "title" => Ok(FilmField::Title),
+            "year" => Ok(FilmField::Year),
+            "director" => Ok(FilmField::Director),
+            "writer" => Ok(FilmField::Writer),
+            "genre" | "genres" => Ok(FilmField::Genre),
+            "star" | "stars" => Ok(FilmField::Stars),
+            "description" => Ok(FilmField::Description),
+            _ => Err(FilmParserError::UnknownField(s.to_string())),
+        }
+    }
+}
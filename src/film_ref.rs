@@ -0,0 +1,331 @@
+//! A film record borrowed from its input.
+//!
+//! A `FilmRef` holds `&str` slices of the record line, taken from the spans the grammar matched,
+//! so scanning records copies no text. Only the films that are kept need to be turned into
+//! owned `Film`s with `FilmRef::to_owned`, e.g. by `sources::scan_file`. `Film` itself is parsed
+//! through a `FilmRef`, so both accept and reject exactly the same records.
+
+use crate::{Film, FilmField, FilmParser, FilmParserError, ParseOptions, Rule, WarningKind};
+use pest::iterators::Pair;
+use pest::Parser;
+use std::slice;
+
+/// A film whose text fields borrow from the record line it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilmRef<'a> {
+    /// The title of the film.
+    pub title: &'a str,
+    /// The release year of the film.
+    pub year: u32,
+    /// The director of the film.
+    pub director: &'a str,
+    /// The writer of the film.
+    pub writer: &'a str,
+    /// The genres associated with the film.
+    pub genre: Vec<&'a str>,
+    /// The main cast of the film.
+    pub stars: Vec<&'a str>,
+    /// A brief description of the film.
+    pub description: &'a str,
+}
+
+impl<'a> FilmRef<'a> {
+    /// Parses one record line with the English keys; `ParseOptions::keys` is not applied, see
+    /// `keys::KeyTable::normalise`. Errors are reported like `parse_film_lines_with_warnings`.
+    pub fn parse(
+        line: &'a str,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<WarningKind>), FilmParserError> {
+        let pair = FilmParser::parse(Rule::file, line)
+            .ok()
+            .and_then(|mut pairs| pairs.next())
+            .ok_or_else(|| FilmParserError::ParsingError(line.to_string()))?;
+        Self::parse_with_warnings(pair, options)
+            .map_err(|err| FilmParserError::RecordError(line.to_string(), Box::new(err)))
+    }
+
+    /// Copies the borrowed text into an owned `Film` without a source.
+    pub fn to_owned(&self) -> Film {
+        Film::new(
+            self.title.to_string(),
+            self.year,
+            self.director.to_string(),
+            self.writer.to_string(),
+            self.genre.iter().map(|genre| genre.to_string()).collect(),
+            self.stars.iter().map(|star| star.to_string()).collect(),
+            self.description.to_string(),
+        )
+    }
+
+    /// Returns the text values of a field other than the year, which has none.
+    /// Single-valued fields yield one value.
+    pub fn text_values(&self, field: FilmField) -> &[&'a str] {
+        match field {
+            FilmField::Title => slice::from_ref(&self.title),
+            FilmField::Year => &[],
+            FilmField::Director => slice::from_ref(&self.director),
+            FilmField::Writer => slice::from_ref(&self.writer),
+            FilmField::Genre => &self.genre,
+            FilmField::Stars => &self.stars,
+            FilmField::Description => slice::from_ref(&self.description),
+        }
+    }
+
+    fn parse_string_field(inner_pair: Pair<'a, Rule>, target_rule: Rule) -> &'a str {
+        inner_pair
+            .into_inner()
+            .find(|pair| pair.as_rule() == target_rule)
+            .map_or("", |pair| pair.as_str())
+    }
+
+    /// Collects the trimmed items of a list field. Empty items are an error in strict mode and
+    /// dropped with a warning in lenient mode. `list` names the field and `index` counts from 1.
+    fn parse_vector_field(
+        inner_pair: Pair<'a, Rule>,
+        list_rule: Rule,
+        list: &str,
+        options: &ParseOptions,
+        warnings: &mut Vec<WarningKind>,
+    ) -> Result<Vec<&'a str>, FilmParserError> {
+        let Some(list_pair) = inner_pair
+            .into_inner()
+            .find(|pair| pair.as_rule() == list_rule)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut items = Vec::new();
+        for (index, item) in list_pair.into_inner().enumerate() {
+            let value = item.as_str().trim_matches('"').trim();
+            if value.is_empty() {
+                if !options.is_lenient() {
+                    return Err(FilmParserError::EmptyListItem(list.to_string(), index + 1));
+                }
+                warnings.push(WarningKind::EmptyListItem(list.to_string(), index + 1));
+                continue;
+            }
+            items.push(value);
+        }
+        Ok(items)
+    }
+
+    /// Returns the field parsed by a field rule such as `Rule::Title`.
+    fn field_of_rule(rule: Rule) -> Option<FilmField> {
+        match rule {
+            Rule::Title => Some(FilmField::Title),
+            Rule::Year => Some(FilmField::Year),
+            Rule::Director => Some(FilmField::Director),
+            Rule::Writer => Some(FilmField::Writer),
+            Rule::Genre => Some(FilmField::Genre),
+            Rule::Stars => Some(FilmField::Stars),
+            Rule::Description => Some(FilmField::Description),
+            _ => None,
+        }
+    }
+
+    /// Checks that a field key is in title case and followed by `: `.
+    fn check_key(
+        field_pair: &Pair<Rule>,
+        field: FilmField,
+        options: &ParseOptions,
+        warnings: &mut Vec<WarningKind>,
+    ) -> Result<(), FilmParserError> {
+        for pair in field_pair.clone().into_inner() {
+            let text = pair.as_str();
+            let warning = match pair.as_rule() {
+                Rule::key_separator
+                    if !(text.starts_with(": ") && text[1..].chars().all(|c| c == ' ')) =>
+                {
+                    WarningKind::KeyWhitespace(field)
+                }
+                Rule::title_key
+                | Rule::year_key
+                | Rule::director_key
+                | Rule::writer_key
+                | Rule::genre_key
+                | Rule::stars_key
+                | Rule::description_key
+                    if text != field.key() =>
+                {
+                    WarningKind::KeyCase(text.to_string())
+                }
+                _ => continue,
+            };
+            Self::tolerate(warning, options, warnings)?;
+        }
+        Ok(())
+    }
+
+    /// Records a warning in lenient mode and returns an error in strict mode.
+    fn tolerate(
+        warning: WarningKind,
+        options: &ParseOptions,
+        warnings: &mut Vec<WarningKind>,
+    ) -> Result<(), FilmParserError> {
+        if options.is_lenient() {
+            warnings.push(warning);
+            Ok(())
+        } else {
+            Err(FilmParserError::StrictModeError(warning))
+        }
+    }
+
+    /// Builds a `FilmRef` from a parsed `file` pair and returns the problems tolerated in
+    /// lenient mode. In strict mode the warnings are always empty.
+    pub fn parse_with_warnings(
+        pair: Pair<'a, Rule>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<WarningKind>), FilmParserError> {
+        if pair.as_str().trim().is_empty() {
+            return Err(FilmParserError::ParsingError(
+                "Empty input was provided".to_string(),
+            ));
+        }
+
+        let mut title = "";
+        let mut year = 0;
+        let mut director = "";
+        let mut writer = "";
+        let mut genre = Vec::new();
+        let mut stars = Vec::new();
+        let mut description = "";
+        let mut warnings = Vec::new();
+
+        for inner_pair in pair.into_inner() {
+            match inner_pair.as_rule() {
+                Rule::film => {
+                    for inner_pair_1 in inner_pair.into_inner() {
+                        if let Some(field) = Self::field_of_rule(inner_pair_1.as_rule()) {
+                            Self::check_key(&inner_pair_1, field, options, &mut warnings)?;
+                        }
+                        match inner_pair_1.as_rule() {
+                            Rule::Title => {
+                                title = Self::parse_string_field(inner_pair_1, Rule::title_value)
+                            }
+                            Rule::Year => {
+                                let value = Self::parse_string_field(
+                                    inner_pair_1.clone(),
+                                    Rule::year_value,
+                                );
+                                if let Ok(parsed_year) = value.parse::<u32>() {
+                                    year = parsed_year;
+                                } else {
+                                    return Err(FilmParserError::RuleParsingError(
+                                        "year".to_string(),
+                                        format!("{:?}", inner_pair_1.as_rule()),
+                                    ));
+                                }
+                            }
+                            Rule::Director => {
+                                director =
+                                    Self::parse_string_field(inner_pair_1, Rule::director_value)
+                            }
+                            Rule::Writer => {
+                                writer = Self::parse_string_field(inner_pair_1, Rule::writer_value)
+                            }
+                            Rule::Genre => {
+                                genre = Self::parse_vector_field(
+                                    inner_pair_1,
+                                    Rule::genre_list,
+                                    "genre",
+                                    options,
+                                    &mut warnings,
+                                )?
+                            }
+
+                            Rule::Stars => {
+                                stars = Self::parse_vector_field(
+                                    inner_pair_1,
+                                    Rule::stars_list,
+                                    "stars",
+                                    options,
+                                    &mut warnings,
+                                )?
+                            }
+                            Rule::Description => {
+                                description =
+                                    Self::parse_string_field(inner_pair_1, Rule::description_value)
+                            }
+                            Rule::separator | Rule::record_end => {}
+                            Rule::trailing_text => {
+                                let text = inner_pair_1.as_str().trim();
+                                if !text.is_empty() {
+                                    Self::tolerate(
+                                        WarningKind::TrailingText(text.to_string()),
+                                        options,
+                                        &mut warnings,
+                                    )?;
+                                }
+                            }
+                            _ => {
+                                return Err(FilmParserError::UnknownRule(format!(
+                                    "{:?}",
+                                    inner_pair_1.as_rule()
+                                )));
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    return Err(FilmParserError::UnknownRule(format!(
+                        "{:?}",
+                        inner_pair.as_rule()
+                    )));
+                }
+            }
+        }
+
+        for unknown in genre.iter().filter(|g| !options.is_known_genre(g)) {
+            Self::tolerate(
+                WarningKind::UnknownGenre(unknown.to_string()),
+                options,
+                &mut warnings,
+            )?;
+        }
+
+        if title.is_empty() || year == 0 {
+            return Err(FilmParserError::MissingFieldsError);
+        }
+        let missing = [
+            (FilmField::Director, director.is_empty()),
+            (FilmField::Writer, writer.is_empty()),
+            (FilmField::Genre, genre.is_empty()),
+            (FilmField::Stars, stars.is_empty()),
+            (FilmField::Description, description.is_empty()),
+        ];
+        for (field, is_missing) in missing {
+            if is_missing {
+                if !options.is_lenient() {
+                    return Err(FilmParserError::MissingFieldsError);
+                }
+                warnings.push(WarningKind::MissingField(field));
+            }
+        }
+
+        let film = FilmRef {
+            title,
+            year,
+            director,
+            writer,
+            genre,
+            stars,
+            description,
+        };
+        Ok((film, warnings))
+    }
+}
+
+impl Film {
+    /// Borrows the fields of the film as a `FilmRef`.
+    pub fn as_film_ref(&self) -> FilmRef<'_> {
+        FilmRef {
+            title: &self.title,
+            year: self.year,
+            director: &self.director,
+            writer: &self.writer,
+            genre: self.genre.iter().map(String::as_str).collect(),
+            stars: self.stars.iter().map(String::as_str).collect(),
+            description: &self.description,
+        }
+    }
+}
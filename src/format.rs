@@ -0,0 +1,126 @@
+//! Canonical formatting of catalogue source files.
+//!
+//! The grammar accepts any number of spaces after a `;`, values with surrounding whitespace and
+//! genres in any casing. The canonical form has one record per line, fields separated by `; `,
+//! trimmed values and list items, and genres spelled as in `KNOWN_GENRES`, e.g. `TV Movie`, or
+//! in title case when they are not known, e.g. `Sci-Fi`.
+//!
+//! Text after a record without a description is an error in strict mode; lenient mode only warns,
+//! but formatting would drop that text, so the formatter rejects it too. Other lenient warnings
+//! are returned with the formatted file.
+
+use crate::compression;
+use crate::keys::KeyLanguage;
+use crate::options::KNOWN_GENRES;
+use crate::sources::{parse_source_lines, read_source_lines_with};
+use crate::view::{sort_films, SortKey};
+use crate::{Film, FilmParserError, ParseOptions, ParseWarning, WarningKind};
+use std::path::Path;
+
+/// Options of the canonical formatter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Keys the records are sorted by; the input order is kept when empty.
+    pub sort_keys: Vec<SortKey>,
+    /// How the files are parsed; in lenient mode repaired records are written repaired.
+    pub parse: ParseOptions,
+    /// Language of the keys written; English when `None`.
+    pub keys: Option<KeyLanguage>,
+}
+
+/// The result of formatting a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedFile {
+    /// The canonical content of the file.
+    pub content: String,
+    /// Whether the canonical content differs from the file.
+    pub changed: bool,
+    /// Problems repaired in lenient mode; always empty in strict mode.
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// Returns the film with trimmed values and list items and canonically spelled genres.
+pub fn canonical_film(film: &Film) -> Film {
+    let mut canonical = film.clone();
+    canonical.title = film.title.trim().to_string();
+    canonical.director = film.director.trim().to_string();
+    canonical.writer = film.writer.trim().to_string();
+    canonical.genre = film
+        .genre
+        .iter()
+        .map(|genre| canonical_genre(genre))
+        .collect();
+    canonical.stars = film
+        .stars
+        .iter()
+        .map(|star| star.trim().to_string())
+        .collect();
+    canonical.description = film.description.trim().to_string();
+    canonical
+}
+
+/// Formats films as canonical record lines, each followed by a line break.
+pub fn format_films(films: &[Film], options: &FormatOptions) -> String {
+    let mut films: Vec<Film> = films.iter().map(canonical_film).collect();
+    sort_films(&mut films, &options.sort_keys);
+    films
+        .iter()
+        .map(|film| match &options.keys {
+            Some(language) => format!("{}\n", film.to_record_line_in(language)),
+            None => format!("{}\n", film.to_record_line()),
+        })
+        .collect()
+}
+
+/// Parses a file and returns its canonical content. Blank lines are dropped.
+/// Fails if a line holds text after its record, which formatting would drop. The content is
+/// UTF-8 without a byte order mark, so files in other encodings are always changed.
+pub fn format_file(path: &Path, options: &FormatOptions) -> Result<FormattedFile, FilmParserError> {
+    let lines = read_source_lines_with(path, options.parse.encoding)?;
+    let (films, warnings) = parse_source_lines(lines, &options.parse)?;
+    let lost = warnings.iter().find_map(|warning| match &warning.kind {
+        WarningKind::TrailingText(text) => Some((warning.location.clone(), text.clone())),
+        _ => None,
+    });
+    if let Some((Some(location), text)) = lost {
+        return Err(FilmParserError::AtSource(
+            location,
+            Box::new(FilmParserError::ParsingError(format!(
+                "text after the record would be lost: {}",
+                text
+            ))),
+        ));
+    }
+    let original = compression::read_file(path)?;
+    let content = format_films(&films, options);
+    Ok(FormattedFile {
+        changed: content.as_bytes() != original.as_slice(),
+        content,
+        warnings,
+    })
+}
+
+/// Returns the spelling of the genre in `KNOWN_GENRES` if it matches one in any case, e.g. `tv movie`
+/// to `TV Movie`, and the genre in title case otherwise.
+fn canonical_genre(genre: &str) -> String {
+    let trimmed = genre.trim();
+    KNOWN_GENRES
+        .iter()
+        .find(|known| known.to_lowercase() == trimmed.to_lowercase())
+        .map_or_else(|| title_case(trimmed), |known| known.to_string())
+}
+
+/// Capitalises the first letter of every word and hyphenated part, e.g. `sci-fi` to `Sci-Fi`.
+fn title_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut word_start = true;
+    for c in text.trim().chars() {
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        word_start = c.is_whitespace() || c == '-';
+    }
+    result
+}
@@ -0,0 +1,164 @@
+//! Fuzzy lookup of film titles and people's names, e.g. `Midsomar` or `florence pug`.
+//!
+//! Candidates are ranked by the average of their trigram similarity and their normalised
+//! edit distance to the query, both computed on lowercase text.
+
+use crate::Film;
+use std::collections::HashSet;
+
+/// Matches scoring below this are not returned.
+pub const MIN_SCORE: f64 = 0.4;
+
+/// What a fuzzy match refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchKind {
+    /// A film title.
+    Title,
+    /// A director, writer or star.
+    Person,
+}
+
+/// A candidate found by a fuzzy lookup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// The title or name as written in the catalogue.
+    pub text: String,
+    /// Whether the text is a title or a name.
+    pub kind: MatchKind,
+    /// Similarity from 0 to 1; 1 is an exact match ignoring case.
+    pub score: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Candidate {
+    text: String,
+    normalized: Vec<char>,
+    trigrams: HashSet<String>,
+    kind: MatchKind,
+}
+
+/// Fuzzy matcher over the titles and people of a catalogue.
+#[derive(Debug, Clone, Default)]
+pub struct FuzzyMatcher {
+    candidates: Vec<Candidate>,
+}
+
+impl FuzzyMatcher {
+    /// Collects the distinct titles and people of the films.
+    pub fn new(films: &[Film]) -> Self {
+        let mut matcher = FuzzyMatcher::default();
+        let mut seen: HashSet<(String, MatchKind)> = HashSet::new();
+        for film in films {
+            let people = [&film.director, &film.writer]
+                .into_iter()
+                .chain(film.stars.iter())
+                .map(|name| (name, MatchKind::Person));
+            for (text, kind) in std::iter::once((&film.title, MatchKind::Title)).chain(people) {
+                let normalized = normalize(text);
+                if normalized.is_empty() || !seen.insert((normalized.clone(), kind)) {
+                    continue;
+                }
+                matcher.candidates.push(Candidate {
+                    text: text.trim().to_string(),
+                    trigrams: trigrams(&normalized),
+                    normalized: normalized.chars().collect(),
+                    kind,
+                });
+            }
+        }
+        matcher
+    }
+
+    /// Returns up to `limit` titles and names similar to the query, best first.
+    /// When `kind` is set, only candidates of that kind are considered.
+    pub fn lookup(&self, query: &str, kind: Option<MatchKind>, limit: usize) -> Vec<FuzzyMatch> {
+        let normalized = normalize(query);
+        if normalized.is_empty() {
+            return Vec::new();
+        }
+        let query_chars: Vec<char> = normalized.chars().collect();
+        let query_trigrams = trigrams(&normalized);
+
+        let mut matches: Vec<FuzzyMatch> = self
+            .candidates
+            .iter()
+            .filter(|candidate| kind.is_none_or(|kind| candidate.kind == kind))
+            .map(|candidate| FuzzyMatch {
+                text: candidate.text.clone(),
+                kind: candidate.kind,
+                score: (trigram_similarity(&query_trigrams, &candidate.trigrams)
+                    + edit_similarity(&query_chars, &candidate.normalized))
+                    / 2.0,
+            })
+            .filter(|found| found.score >= MIN_SCORE)
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.text.cmp(&b.text))
+        });
+        matches.truncate(limit);
+        matches
+    }
+
+    /// Returns the best match of the given kind, for "did you mean" suggestions.
+    pub fn suggest(&self, query: &str, kind: MatchKind) -> Option<String> {
+        self.lookup(query, Some(kind), 1)
+            .into_iter()
+            .next()
+            .map(|found| found.text)
+    }
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    levenshtein(&a, &b)
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+fn edit_similarity(a: &[char], b: &[char]) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / longest as f64
+}
+
+/// Dice coefficient of two trigram sets.
+fn trigram_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    2.0 * a.intersection(b).count() as f64 / (a.len() + b.len()) as f64
+}
+
+/// Trigrams of a string padded with two spaces in front and one behind.
+fn trigrams(text: &str) -> HashSet<String> {
+    let padded: Vec<char> = format!("  {} ", text).chars().collect();
+    padded
+        .windows(3)
+        .map(|window| window.iter().collect())
+        .collect()
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
@@ -0,0 +1,274 @@
+//! Collaboration graph built from a collection of films.
+//!
+//! Nodes are people (directors, writers and stars) and films, and every credit of a person on a
+//! film is an edge between the two. A person credited in several roles is a single node.
+
+use crate::{Film, FilmParserError};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+/// The role a person is credited with on a film.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Role {
+    /// `Film::director`
+    Director,
+    /// `Film::writer`
+    Writer,
+    /// One of `Film::stars`
+    Star,
+}
+
+impl Role {
+    /// Returns the lowercase name of the role.
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Director => "director",
+            Role::Writer => "writer",
+            Role::Star => "star",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Role {
+    type Err = FilmParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "director" | "directors" => Ok(Role::Director),
+            "writer" | "writers" => Ok(Role::Writer),
+            "star" | "stars" | "actor" | "actors" => Ok(Role::Star),
+            _ => Err(FilmParserError::InvalidOption(format!(
+                "role {}, expected director, writer or star",
+                s
+            ))),
+        }
+    }
+}
+
+/// Kind of a graph node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A director, writer or star.
+    Person,
+    /// A film.
+    Film,
+}
+
+/// A node of the collaboration graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    /// Whether the node is a person or a film.
+    pub kind: NodeKind,
+    /// The person's name, or the film title followed by the year, e.g. `Raw (2016)`.
+    pub name: String,
+}
+
+/// An edge of the graph: a person credited on a film.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Credit {
+    /// Index of the person node.
+    pub person: usize,
+    /// Index of the film node.
+    pub film: usize,
+    /// The role the person is credited with.
+    pub role: Role,
+}
+
+/// A person who worked with another person, with the films they share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collaborator {
+    /// The collaborator's name.
+    pub name: String,
+    /// Names of the shared films.
+    pub films: Vec<String>,
+}
+
+/// Graph of people and films connected by credits.
+#[derive(Debug, Clone, Default)]
+pub struct CollaborationGraph {
+    nodes: Vec<Node>,
+    credits: Vec<Credit>,
+    people: HashMap<String, usize>,
+    /// Indices into `credits` for every node.
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl CollaborationGraph {
+    /// Builds the graph from the directors, writers and stars of the films.
+    pub fn build(films: &[Film]) -> Self {
+        let mut graph = CollaborationGraph::default();
+        for film in films {
+            let film_node =
+                graph.add_node(NodeKind::Film, format!("{} ({})", film.title, film.year));
+            let credits = [
+                (&film.director, Role::Director),
+                (&film.writer, Role::Writer),
+            ]
+            .into_iter()
+            .chain(film.stars.iter().map(|star| (star, Role::Star)));
+            for (name, role) in credits {
+                if name.trim().is_empty() {
+                    continue;
+                }
+                let person = graph.person_node(name);
+                let credit = Credit {
+                    person,
+                    film: film_node,
+                    role,
+                };
+                let is_new = graph.adjacency[film_node]
+                    .iter()
+                    .all(|&existing| graph.credits[existing] != credit);
+                if is_new {
+                    graph.adjacency[person].push(graph.credits.len());
+                    graph.adjacency[film_node].push(graph.credits.len());
+                    graph.credits.push(credit);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Returns all nodes; node indices refer to this slice.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns all credits.
+    pub fn credits(&self) -> &[Credit] {
+        &self.credits
+    }
+
+    /// Returns the credits of a node.
+    pub fn credits_of(&self, node: usize) -> impl Iterator<Item = &Credit> + '_ {
+        self.adjacency[node]
+            .iter()
+            .map(|&credit| &self.credits[credit])
+    }
+
+    /// Finds a person by name, ignoring case and surrounding whitespace.
+    pub fn person(&self, name: &str) -> Result<usize, FilmParserError> {
+        self.people
+            .get(&Self::person_key(name))
+            .copied()
+            .ok_or_else(|| FilmParserError::PersonNotFound(name.to_string()))
+    }
+
+    /// Returns the people who worked on films with the given person, most shared films first.
+    /// When `role` is set, only collaborators credited in that role are counted,
+    /// e.g. the stars who worked with a director.
+    pub fn collaborators(
+        &self,
+        name: &str,
+        role: Option<Role>,
+    ) -> Result<Vec<Collaborator>, FilmParserError> {
+        let person = self.person(name)?;
+        let mut shared: HashMap<usize, Vec<usize>> = HashMap::new();
+        for film in self.films_of(person) {
+            for credit in self.credits_of(film) {
+                if credit.person == person || role.is_some_and(|role| credit.role != role) {
+                    continue;
+                }
+                let films = shared.entry(credit.person).or_default();
+                if !films.contains(&film) {
+                    films.push(film);
+                }
+            }
+        }
+
+        let mut collaborators: Vec<Collaborator> = shared
+            .into_iter()
+            .map(|(other, films)| Collaborator {
+                name: self.nodes[other].name.clone(),
+                films: films
+                    .into_iter()
+                    .map(|film| self.nodes[film].name.clone())
+                    .collect(),
+            })
+            .collect();
+        collaborators.sort_by(|a, b| {
+            b.films
+                .len()
+                .cmp(&a.films.len())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(collaborators)
+    }
+
+    /// Finds the shortest chain of shared films between two people.
+    /// The path starts and ends with the given people and alternates people and films.
+    /// Returns `None` when the people are not connected.
+    pub fn shortest_path(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<Vec<&Node>>, FilmParserError> {
+        let start = self.person(from)?;
+        let goal = self.person(to)?;
+
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+
+        while let Some(node) = queue.pop_front() {
+            if node == goal {
+                let mut path = vec![&self.nodes[goal]];
+                let mut current = goal;
+                while let Some(prev) = previous[current] {
+                    path.push(&self.nodes[prev]);
+                    current = prev;
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+            for credit in self.credits_of(node) {
+                let next = if credit.person == node {
+                    credit.film
+                } else {
+                    credit.person
+                };
+                if !visited[next] {
+                    visited[next] = true;
+                    previous[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the film nodes a person is credited on, each film once.
+    pub fn films_of(&self, person: usize) -> Vec<usize> {
+        let mut films: Vec<usize> = self.credits_of(person).map(|credit| credit.film).collect();
+        films.dedup();
+        films
+    }
+
+    fn add_node(&mut self, kind: NodeKind, name: String) -> usize {
+        self.nodes.push(Node { kind, name });
+        self.adjacency.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    fn person_node(&mut self, name: &str) -> usize {
+        let key = Self::person_key(name);
+        if let Some(&node) = self.people.get(&key) {
+            return node;
+        }
+        let node = self.add_node(NodeKind::Person, name.trim().to_string());
+        self.people.insert(key, node);
+        node
+    }
+
+    fn person_key(name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+}
@@ -0,0 +1,433 @@
+//! Full-text search over titles, descriptions, people and genres.
+//!
+//! `SearchIndex` is an inverted index with term positions, ranked with BM25. Queries are words
+//! that must all match; a word ending in `*` matches any term with that prefix and words in
+//! double quotes must appear next to each other. Terms are Unicode case-folded, so `Straße` and
+//! `STRASSE` are the same term.
+//!
+//! The index can be saved next to the source file (`films.txt.idx` for `films.txt`) together with
+//! a fingerprint of the source and of the parse options, so it is rebuilt only when the source or
+//! the options change.
+
+use crate::sources::parse_file;
+use crate::{Film, FilmParserError, ParseOptions, ParseWarning};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extension appended to the source file name to get the index file name.
+pub const INDEX_EXTENSION: &str = "idx";
+
+/// Version of the saved terms, raised when tokenizing changes so older index files are rebuilt.
+const INDEX_FORMAT: u32 = 1;
+
+/// BM25 term frequency saturation.
+const K1: f64 = 1.2;
+
+/// BM25 document length normalisation.
+const B: f64 = 0.75;
+
+/// Size and content hash of the file an index was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceFingerprint {
+    /// File size in bytes.
+    pub len: u64,
+    /// 64-bit FNV-1a hash of the file content.
+    pub hash: u64,
+}
+
+impl SourceFingerprint {
+    /// Computes the fingerprint of a file's content.
+    pub fn of_bytes(bytes: &[u8]) -> Self {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        SourceFingerprint {
+            len: bytes.len() as u64,
+            hash,
+        }
+    }
+
+    /// Computes the fingerprint of the parse options that change the parsed films, so `jobs` is
+    /// left out.
+    pub fn of_options(options: &ParseOptions) -> Self {
+        let description = format!(
+            "{:?}|{:?}|{:?}|{}",
+            options.mode,
+            options.known_genres,
+            options.keys,
+            options.encoding.map_or("", |encoding| encoding.name())
+        );
+        Self::of_bytes(description.as_bytes())
+    }
+
+    /// Reads a file and computes its fingerprint.
+    pub fn of_file(path: &Path) -> Result<Self, FilmParserError> {
+        fs::read(path)
+            .map(|bytes| Self::of_bytes(&bytes))
+            .map_err(|e| FilmParserError::FileReadingError(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// Stored fields of an indexed film.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedFilm {
+    /// The film title.
+    pub title: String,
+    /// The release year.
+    pub year: u32,
+    /// Number of indexed tokens.
+    length: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Posting {
+    film: usize,
+    positions: Vec<u32>,
+}
+
+/// A film matching a search, with its BM25 score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    /// Position of the film in the indexed list.
+    pub film: usize,
+    /// The film title.
+    pub title: String,
+    /// The release year.
+    pub year: u32,
+    /// BM25 score; higher is better.
+    pub score: f64,
+}
+
+/// One part of a search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryClause {
+    /// A single term.
+    Term(String),
+    /// Any term starting with the prefix.
+    Prefix(String),
+    /// Terms that must appear in this order next to each other.
+    Phrase(Vec<String>),
+}
+
+/// An index returned by `SearchIndex::open_or_build`.
+#[derive(Debug)]
+pub struct OpenedIndex {
+    /// The loaded or rebuilt index.
+    pub index: SearchIndex,
+    /// Whether the index was rebuilt from the source.
+    pub rebuilt: bool,
+    /// Problems repaired in lenient mode while rebuilding; empty when the index was loaded.
+    pub warnings: Vec<ParseWarning>,
+    /// Why the rebuilt index could not be saved; the index is only kept in memory then.
+    pub save_error: Option<FilmParserError>,
+}
+
+/// Inverted index over the films of a catalogue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    source: Option<SourceFingerprint>,
+    #[serde(default)]
+    options: Option<SourceFingerprint>,
+    #[serde(default)]
+    format: u32,
+    films: Vec<IndexedFilm>,
+    average_length: f64,
+    terms: BTreeMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    /// Builds an index over the title, description, people and genres of the films.
+    pub fn build(films: &[Film]) -> Self {
+        let mut terms: BTreeMap<String, Vec<Posting>> = BTreeMap::new();
+        let mut indexed = Vec::with_capacity(films.len());
+
+        for (index, film) in films.iter().enumerate() {
+            let mut segments = vec![
+                film.title.as_str(),
+                film.description.as_str(),
+                film.director.as_str(),
+                film.writer.as_str(),
+            ];
+            segments.extend(film.stars.iter().map(String::as_str));
+            segments.extend(film.genre.iter().map(String::as_str));
+
+            let mut positions: HashMap<String, Vec<u32>> = HashMap::new();
+            let mut position = 0u32;
+            for segment in segments {
+                for token in tokenize(segment) {
+                    positions.entry(token).or_default().push(position);
+                    position += 1;
+                }
+                // Leave a gap so that phrases do not match across fields or names.
+                position += 1;
+            }
+
+            let length = positions.values().map(|p| p.len() as u32).sum();
+            for (term, positions) in positions {
+                terms.entry(term).or_default().push(Posting {
+                    film: index,
+                    positions,
+                });
+            }
+            indexed.push(IndexedFilm {
+                title: film.title.clone(),
+                year: film.year,
+                length,
+            });
+        }
+
+        let total_length: u32 = indexed.iter().map(|film| film.length).sum();
+        SearchIndex {
+            source: None,
+            options: None,
+            format: INDEX_FORMAT,
+            average_length: if indexed.is_empty() {
+                0.0
+            } else {
+                total_length as f64 / indexed.len() as f64
+            },
+            films: indexed,
+            terms,
+        }
+    }
+
+    /// Returns the path of the index file for a source file.
+    pub fn index_path(source: &Path) -> PathBuf {
+        let mut name = source.as_os_str().to_owned();
+        name.push(".");
+        name.push(INDEX_EXTENSION);
+        PathBuf::from(name)
+    }
+
+    /// Loads the index saved next to the source file, or parses the source with `options` and
+    /// builds and saves the index when it is missing or the source or the options have changed.
+    /// An index that cannot be saved is still returned, with the error in `save_error`.
+    pub fn open_or_build(
+        source: &str,
+        options: &ParseOptions,
+    ) -> Result<OpenedIndex, FilmParserError> {
+        let source_path = Path::new(source);
+        if !source_path.exists() {
+            return Err(FilmParserError::NoFileFound(source.to_string()));
+        }
+        let fingerprint = Some(SourceFingerprint::of_file(source_path)?);
+        let options_fingerprint = Some(SourceFingerprint::of_options(options));
+        let index_path = Self::index_path(source_path);
+
+        if let Ok(saved) = Self::load(&index_path) {
+            if saved.format == INDEX_FORMAT
+                && saved.source == fingerprint
+                && saved.options == options_fingerprint
+            {
+                return Ok(OpenedIndex {
+                    index: saved,
+                    rebuilt: false,
+                    warnings: Vec::new(),
+                    save_error: None,
+                });
+            }
+        }
+
+        let (films, warnings) = parse_file(source_path, options)?;
+        let mut index = Self::build(&films);
+        index.source = fingerprint;
+        index.options = options_fingerprint;
+        let save_error = index.save(&index_path).err();
+        Ok(OpenedIndex {
+            index,
+            rebuilt: true,
+            warnings,
+            save_error,
+        })
+    }
+
+    /// Reads an index file.
+    pub fn load(path: &Path) -> Result<Self, FilmParserError> {
+        let content = fs::read_to_string(path)
+            .map_err(|_| FilmParserError::FileOpeningError(path.display().to_string()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| FilmParserError::ParsingError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Writes the index to a file.
+    pub fn save(&self, path: &Path) -> Result<(), FilmParserError> {
+        let content = serde_json::to_string(self)
+            .map_err(|e| FilmParserError::SerializationError(e.to_string()))?;
+        fs::write(path, content)
+            .map_err(|_| FilmParserError::FileWritingError(path.display().to_string()))
+    }
+
+    /// Returns the number of indexed films.
+    pub fn len(&self) -> usize {
+        self.films.len()
+    }
+
+    /// Returns `true` if no films are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.films.is_empty()
+    }
+
+    /// Searches the index and returns up to `limit` films matching every clause, best first.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let clauses = parse_query(query);
+        if clauses.is_empty() || self.films.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for (position, clause) in clauses.iter().enumerate() {
+            let clause_scores = self.score_clause(clause);
+            if position == 0 {
+                scores = clause_scores;
+            } else {
+                scores.retain(|film, _| clause_scores.contains_key(film));
+                for (film, score) in scores.iter_mut() {
+                    *score += clause_scores[film];
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(film, score)| SearchHit {
+                film,
+                title: self.films[film].title.clone(),
+                year: self.films[film].year,
+                score,
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.film.cmp(&b.film))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// BM25 scores of the films matching a clause.
+    fn score_clause(&self, clause: &QueryClause) -> HashMap<usize, f64> {
+        let mut scores = HashMap::new();
+        match clause {
+            QueryClause::Term(term) => {
+                if let Some(postings) = self.terms.get(term) {
+                    self.add_scores(
+                        &mut scores,
+                        postings.iter().map(|p| (p.film, p.positions.len())),
+                    );
+                }
+            }
+            QueryClause::Prefix(prefix) => {
+                for (term, postings) in self.terms.range(prefix.clone()..) {
+                    if !term.starts_with(prefix.as_str()) {
+                        break;
+                    }
+                    self.add_scores(
+                        &mut scores,
+                        postings.iter().map(|p| (p.film, p.positions.len())),
+                    );
+                }
+            }
+            QueryClause::Phrase(words) => {
+                let matches = self.phrase_matches(words);
+                self.add_scores(&mut scores, matches.into_iter());
+            }
+        }
+        scores
+    }
+
+    /// Adds BM25 scores for `(film, term frequency)` pairs of one term or phrase.
+    fn add_scores(
+        &self,
+        scores: &mut HashMap<usize, f64>,
+        frequencies: impl Iterator<Item = (usize, usize)>,
+    ) {
+        let frequencies: Vec<(usize, usize)> = frequencies.collect();
+        let total = self.films.len() as f64;
+        let matching = frequencies.len() as f64;
+        let idf = ((total - matching + 0.5) / (matching + 0.5) + 1.0).ln();
+
+        for (film, frequency) in frequencies {
+            let frequency = frequency as f64;
+            let length = self.films[film].length as f64;
+            let normalization = K1 * (1.0 - B + B * length / self.average_length.max(1.0));
+            *scores.entry(film).or_insert(0.0) +=
+                idf * frequency * (K1 + 1.0) / (frequency + normalization);
+        }
+    }
+
+    /// Films containing the words as a phrase, with the number of occurrences.
+    fn phrase_matches(&self, words: &[String]) -> Vec<(usize, usize)> {
+        let Some(first) = words.first().and_then(|word| self.terms.get(word)) else {
+            return Vec::new();
+        };
+        let mut rest: Vec<HashMap<usize, &Vec<u32>>> = Vec::new();
+        for word in &words[1..] {
+            let Some(postings) = self.terms.get(word) else {
+                return Vec::new();
+            };
+            rest.push(postings.iter().map(|p| (p.film, &p.positions)).collect());
+        }
+
+        first
+            .iter()
+            .filter_map(|posting| {
+                let count = posting
+                    .positions
+                    .iter()
+                    .filter(|&&start| {
+                        rest.iter().enumerate().all(|(offset, postings)| {
+                            postings.get(&posting.film).is_some_and(|positions| {
+                                positions
+                                    .binary_search(&(start + offset as u32 + 1))
+                                    .is_ok()
+                            })
+                        })
+                    })
+                    .count();
+                (count > 0).then_some((posting.film, count))
+            })
+            .collect()
+    }
+}
+
+/// Splits text into case-folded words.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(caseless::default_case_fold_str)
+        .collect()
+}
+
+/// Splits a search query into terms, prefixes (`word*`) and phrases (`"two words"`).
+pub fn parse_query(query: &str) -> Vec<QueryClause> {
+    let mut clauses = Vec::new();
+    for (position, part) in query.split('"').enumerate() {
+        if position % 2 == 1 {
+            let words = tokenize(part);
+            match words.len() {
+                0 => {}
+                1 => clauses.push(QueryClause::Term(words[0].clone())),
+                _ => clauses.push(QueryClause::Phrase(words)),
+            }
+            continue;
+        }
+        for word in part.split_whitespace() {
+            let is_prefix = word.ends_with('*');
+            let tokens = tokenize(word);
+            if let Some((last, others)) = tokens.split_last() {
+                clauses.extend(others.iter().cloned().map(QueryClause::Term));
+                clauses.push(if is_prefix {
+                    QueryClause::Prefix(last.clone())
+                } else {
+                    QueryClause::Term(last.clone())
+                });
+            }
+        }
+    }
+    clauses
+}
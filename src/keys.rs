@@ -0,0 +1,227 @@
+//! Localised field keys.
+//!
+//! A `KeyTable` lists the keys of every field in several languages. When records are parsed with
+//! a table (`ParseOptions::with_key_table`), keys of any of its languages are matched ignoring case
+//! and rewritten to the English keys before the grammar runs, so `Назва:`, `Título:` and `title:`
+//! all read as `Title:`. Records are written with the keys of one language by
+//! `Film::to_record_line_in`.
+//!
+//! A table file adds languages to the built-in English, Ukrainian and Spanish ones, one
+//! `language = keys` line per language with the keys in record order. Empty lines and lines
+//! starting with `#` are ignored:
+//!
+//! ```text
+//! # language = Title, Year, Director, Writer, Genre, Stars, Description
+//! pl = Tytuł, Rok, Reżyser, Scenarzysta, Gatunek, Obsada, Opis
+//! ```
+
+use crate::{FilmField, FilmParserError};
+use std::fs;
+use std::path::Path;
+
+/// Keys of the built-in languages, in the order of `FilmField::ALL`.
+pub const BUILTIN_LANGUAGES: [(&str, [&str; 7]); 3] = [
+    (
+        "en",
+        [
+            "Title",
+            "Year",
+            "Director",
+            "Writer",
+            "Genre",
+            "Stars",
+            "Description",
+        ],
+    ),
+    (
+        "uk",
+        [
+            "Назва",
+            "Рік",
+            "Режисер",
+            "Сценарист",
+            "Жанр",
+            "Актори",
+            "Опис",
+        ],
+    ),
+    (
+        "es",
+        [
+            "Título",
+            "Año",
+            "Director",
+            "Guionista",
+            "Género",
+            "Reparto",
+            "Descripción",
+        ],
+    ),
+];
+
+/// The keys of the fields in one language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyLanguage {
+    /// Language code, e.g. `uk`.
+    pub code: String,
+    keys: Vec<String>,
+}
+
+impl KeyLanguage {
+    /// Creates a language from its keys, given in the order of `FilmField::ALL`.
+    pub fn new<S: AsRef<str>>(code: &str, keys: &[S]) -> Result<Self, FilmParserError> {
+        let keys: Vec<String> = keys
+            .iter()
+            .map(|key| key.as_ref().trim().to_string())
+            .collect();
+        if keys.len() != FilmField::ALL.len() || keys.iter().any(|key| key.is_empty()) {
+            return Err(FilmParserError::InvalidOption(format!(
+                "keys of language {}, expected {} keys",
+                code,
+                FilmField::ALL.len()
+            )));
+        }
+        Ok(KeyLanguage {
+            code: code.trim().to_string(),
+            keys,
+        })
+    }
+
+    /// The English keys used by the grammar.
+    pub fn english() -> Self {
+        KeyTable::builtin().languages.swap_remove(0)
+    }
+
+    /// Returns the key of the field in this language.
+    pub fn key(&self, field: FilmField) -> &str {
+        let index = FilmField::ALL
+            .iter()
+            .position(|f| *f == field)
+            .unwrap_or_default();
+        &self.keys[index]
+    }
+}
+
+/// Field keys in several languages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyTable {
+    languages: Vec<KeyLanguage>,
+}
+
+impl KeyTable {
+    /// The built-in English, Ukrainian and Spanish keys.
+    pub fn builtin() -> Self {
+        let languages = BUILTIN_LANGUAGES
+            .iter()
+            .map(|(code, keys)| KeyLanguage {
+                code: code.to_string(),
+                keys: keys.iter().map(|key| key.to_string()).collect(),
+            })
+            .collect();
+        KeyTable { languages }
+    }
+
+    /// Parses a table with one `language = keys` line per language.
+    pub fn parse(content: &str) -> Result<Self, FilmParserError> {
+        let mut table = KeyTable::default();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |e: FilmParserError| {
+                FilmParserError::ConfigError(format!("line {}: {}", index + 1, e))
+            };
+            let Some((code, keys)) = line.split_once('=') else {
+                return Err(invalid(FilmParserError::InvalidOption(format!(
+                    "'{}', expected language = keys",
+                    line
+                ))));
+            };
+            let keys: Vec<&str> = keys.split(',').collect();
+            table.add(KeyLanguage::new(code, &keys).map_err(invalid)?);
+        }
+        Ok(table)
+    }
+
+    /// Reads a table file and adds its languages to the built-in ones.
+    pub fn load(path: &Path) -> Result<Self, FilmParserError> {
+        let content = fs::read_to_string(path)
+            .map_err(|_| FilmParserError::FileOpeningError(path.display().to_string()))?;
+        let parsed = Self::parse(&content).map_err(|e| match e {
+            FilmParserError::ConfigError(message) => {
+                FilmParserError::ConfigError(format!("{} {}", path.display(), message))
+            }
+            e => e,
+        })?;
+        let mut table = Self::builtin();
+        for language in parsed.languages {
+            table.add(language);
+        }
+        Ok(table)
+    }
+
+    /// Adds a language, replacing a language with the same code.
+    pub fn add(&mut self, language: KeyLanguage) {
+        self.languages.retain(|known| known.code != language.code);
+        self.languages.push(language);
+    }
+
+    /// Returns the language with the given code.
+    pub fn language(&self, code: &str) -> Result<&KeyLanguage, FilmParserError> {
+        self.languages
+            .iter()
+            .find(|language| language.code.eq_ignore_ascii_case(code.trim()))
+            .ok_or_else(|| FilmParserError::InvalidOption(format!("key language {}", code)))
+    }
+
+    /// Returns the field a key of any language stands for, ignoring case.
+    pub fn field(&self, key: &str) -> Option<FilmField> {
+        let key = key.trim().to_lowercase();
+        self.languages.iter().find_map(|language| {
+            FilmField::ALL
+                .into_iter()
+                .find(|field| language.key(*field).to_lowercase() == key)
+        })
+    }
+
+    /// Rewrites the keys of a record line to the English keys. Text that does not start with a
+    /// known key, such as values holding `:`, is left alone, and so is everything after the
+    /// description key, since the description may contain `;` followed by key-like words.
+    pub fn normalise(&self, line: &str) -> String {
+        let mut result = String::with_capacity(line.len());
+        let mut rest = line;
+        loop {
+            let (segment, tail) = match rest.split_once(';') {
+                Some((segment, tail)) => (segment, Some(tail)),
+                None => (rest, None),
+            };
+            let body = segment.trim_start();
+            result.push_str(&segment[..segment.len() - body.len()]);
+            let known = body
+                .split_once(':')
+                .and_then(|(key, _)| self.field(key).map(|field| (key, field)));
+            let description = match known {
+                Some((key, field)) => {
+                    result.push_str(field.key());
+                    result.push_str(&body[key.trim_end().len()..]);
+                    field == FilmField::Description
+                }
+                None => {
+                    result.push_str(body);
+                    false
+                }
+            };
+            let Some(tail) = tail else {
+                break;
+            };
+            result.push(';');
+            if description {
+                result.push_str(tail);
+                break;
+            }
+            rest = tail;
+        }
+        result
+    }
+}
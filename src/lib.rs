@@ -2,16 +2,46 @@
 //! This crate includes functionality to read, parse, and write film data such as title, director,
 //! writer, genre, stars and description into a `Film` struct for easy access and manipulation.
 
-use crate::FilmParserError::FileReadingError;
-use pest::Parser;
 use pest_derive::Parser;
-use std::fs::File;
+use serde::Serialize;
 use std::io::Write;
-use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::*;
 use thiserror::Error;
 
+pub mod chart;
+pub mod compression;
+pub mod dedupe;
+pub mod diff;
+pub mod encoding;
+pub mod export;
+pub mod field;
+pub mod film_ref;
+pub mod format;
+pub mod fuzzy;
+pub mod graph;
+pub mod index;
+pub mod keys;
+pub mod lint;
+pub mod mapped;
+pub mod messages;
+pub mod options;
+pub mod parallel;
+pub mod query;
+pub mod recommend;
+pub mod sources;
+pub mod stats;
+pub mod synthetic;
+pub mod view;
+
+pub use field::FilmField;
+pub use film_ref::FilmRef;
+pub use graph::CollaborationGraph;
+pub use options::{ParseMode, ParseOptions, ParseWarning, WarningKind};
+pub use query::Query;
+pub use sources::SourceLocation;
+pub use stats::CatalogueStats;
+
 /// Enum representing possible errors that can occur while using the film parser.
 #[derive(Error, Debug)]
 pub enum FilmParserError {
@@ -43,6 +73,10 @@ pub enum FilmParserError {
     #[error("Failed to parse the rule {0} content: {1}")]
     RuleParsingError(String, String),
 
+    /// Error: empty item in a genre or stars list
+    #[error("Item {1} of the {0} list is empty")]
+    EmptyListItem(String, usize),
+
     /// Error: missing required film fields
     #[error("Missing required film fields")]
     MissingFieldsError,
@@ -50,20 +84,73 @@ pub enum FilmParserError {
     /// Error: unknown rule
     #[error("Unknown rule {0}")]
     UnknownRule(String),
+
+    /// Error: unknown film field name
+    #[error("Unknown film field {0}")]
+    UnknownField(String),
+
+    /// Error: invalid command option value
+    #[error("Invalid option {0}")]
+    InvalidOption(String),
+
+    /// Error: no film with the given title
+    #[error("No film titled {0}")]
+    FilmNotFound(String),
+
+    /// Error: person does not appear in any film
+    #[error("No films found for {0}")]
+    PersonNotFound(String),
+
+    /// Error: failed to serialize the output
+    #[error("Failed to serialize the output: {0}")]
+    SerializationError(String),
+
+    /// Error: error in a record of a source file
+    #[error("{0}: {1}")]
+    AtSource(SourceLocation, Box<FilmParserError>),
+
+    /// Error: a record that only lenient mode accepts was parsed in strict mode
+    #[error("Rejected in strict mode: {0}")]
+    StrictModeError(WarningKind),
+
+    /// Error: invalid configuration file
+    #[error("Invalid configuration {0}")]
+    ConfigError(String),
+
+    /// Error: invalid filter query
+    #[error("Invalid query:\n{0}")]
+    QueryError(String),
+
+    /// Error: a record line that matched the grammar but could not be turned into a film
+    #[error("Failed to parse the file content: {0} - {1}")]
+    RecordError(String, Box<FilmParserError>),
 }
 
-/// Reads lines from a specified file.
+/// File the formatted results of `parse_films` are written to.
+pub const RESULT_FILE: &str = "data/result_file.txt";
+
+/// File the unformatted results of `parse_films` are written to.
+pub const RESULT_WO_FORMATING_FILE: &str = "data/result_wo_formating_file.txt";
+
+/// Reads lines from a specified file. A byte order mark selects UTF-8 or UTF-16 and is dropped;
+/// files without one are read as UTF-8.
 pub fn read_lines(filename: &str) -> Result<Vec<String>, FilmParserError> {
+    read_lines_with(filename, None)
+}
+
+/// Reads lines from a specified file, decoding files without a byte order mark with the given
+/// encoding, see `encoding::decode`.
+pub fn read_lines_with(
+    filename: &str,
+    encoding: Option<&'static encoding_rs::Encoding>,
+) -> Result<Vec<String>, FilmParserError> {
     let path = Path::new(filename);
     if !path.exists() {
         return Err(FilmParserError::NoFileFound(filename.to_string()));
     }
 
-    let file = File::open(filename)
-        .map_err(|_| FilmParserError::FileOpeningError(filename.to_string()))?;
-    let reader = BufReader::new(file);
-    let lines: Result<Vec<String>, io::Error> = reader.lines().collect();
-    lines.map_err(|e| FileReadingError(format!("{}: {}", filename, e)))
+    let content = encoding::read_file(path, encoding)?;
+    Ok(content.lines().map(String::from).collect())
 }
 
 #[derive(Parser)]
@@ -74,7 +161,7 @@ pub struct FilmParser;
 /// Represents a film with structured data fields.
 /// Each field captures a different piece of film information, such as the title, release year,
 /// director, writer, genre, stars and description.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Film {
     /// The title of the film.
     pub title: String,
@@ -90,6 +177,8 @@ pub struct Film {
     pub stars: Vec<String>,
     /// A brief description of the film.
     pub description: String,
+    /// Where the record was read from, if known.
+    pub source: Option<SourceLocation>,
 }
 
 /// Creates a new `Film` instance.
@@ -111,164 +200,155 @@ impl Film {
             genre,
             stars,
             description,
+            source: None,
         }
     }
 
-    fn parse_string_field(inner_pair: pest::iterators::Pair<Rule>, target_rule: Rule) -> String {
-        inner_pair
-            .into_inner()
-            .find_map(|pair| {
-                if pair.as_rule() == target_rule {
-                    Some(pair.as_str().to_string())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_default()
+    /// Formats the film as a record line in the input format, e.g.
+    /// `Title: Raw; Year: 2016; ...; Genre: [Drama, Horror]; ...; Description: ...`.
+    pub fn to_record_line(&self) -> String {
+        self.to_record_line_in(&keys::KeyLanguage::english())
     }
 
-    fn parse_vector_field(inner_pair: pest::iterators::Pair<Rule>, list_rule: Rule) -> Vec<String> {
-        inner_pair
-            .into_inner()
-            .find_map(|pair| {
-                if pair.as_rule() == list_rule {
-                    Some(
-                        pair.into_inner()
-                            .flat_map(|item| {
-                                item.as_str()
-                                    .trim_matches('"')
-                                    .split(',')
-                                    .map(|s| s.trim().to_string())
-                                    .collect::<Vec<String>>()
-                            })
-                            .collect::<Vec<String>>(),
-                    )
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_default()
+    /// Formats the film as a record line with the keys of the language, e.g.
+    /// `Назва: Raw; Рік: 2016; ...`.
+    pub fn to_record_line_in(&self, language: &keys::KeyLanguage) -> String {
+        format!(
+            "{}: {}; {}: {}; {}: {}; {}: {}; {}: [{}]; {}: [{}]; {}: {}",
+            language.key(FilmField::Title),
+            self.title,
+            language.key(FilmField::Year),
+            self.year,
+            language.key(FilmField::Director),
+            self.director,
+            language.key(FilmField::Writer),
+            self.writer,
+            language.key(FilmField::Genre),
+            self.genre.join(", "),
+            language.key(FilmField::Stars),
+            self.stars.join(", "),
+            language.key(FilmField::Description),
+            self.description
+        )
     }
 
     pub fn parse_to_struct(pair: pest::iterators::Pair<Rule>) -> Result<Self, FilmParserError> {
-        if pair.as_str().trim().is_empty() {
-            return Err(FilmParserError::ParsingError(
-                "Empty input was provided".to_string(),
-            ));
-        }
+        Self::parse_to_struct_with(pair, &ParseOptions::default())
+    }
 
-        let mut title = String::new();
-        let mut year = 0;
-        let mut director = String::new();
-        let mut writer = String::new();
-        let mut genre = Vec::new();
-        let mut stars = Vec::new();
-        let mut description = String::new();
-
-        for inner_pair in pair.into_inner() {
-            match inner_pair.as_rule() {
-                Rule::film => {
-                    for inner_pair_1 in inner_pair.into_inner() {
-                        match inner_pair_1.as_rule() {
-                            Rule::Title => {
-                                title = Self::parse_string_field(inner_pair_1, Rule::title_value)
-                            }
-                            Rule::Year => {
-                                if let Ok(parsed_year) =
-                                    inner_pair_1.clone().into_inner().as_str().parse::<u32>()
-                                {
-                                    year = parsed_year;
-                                } else {
-                                    return Err(FilmParserError::RuleParsingError(
-                                        "year".to_string(),
-                                        format!("{:?}", inner_pair_1.as_rule()),
-                                    ));
-                                }
-                            }
-                            Rule::Director => {
-                                director =
-                                    Self::parse_string_field(inner_pair_1, Rule::director_value)
-                            }
-                            Rule::Writer => {
-                                writer = Self::parse_string_field(inner_pair_1, Rule::writer_value)
-                            }
-                            Rule::Genre => {
-                                genre = Self::parse_vector_field(inner_pair_1, Rule::genre_list)
-                            }
-
-                            Rule::Stars => {
-                                stars = Self::parse_vector_field(inner_pair_1, Rule::stars_list)
-                            }
-                            Rule::Description => {
-                                description =
-                                    Self::parse_string_field(inner_pair_1, Rule::description_value)
-                            }
-                            _ => {
-                                return Err(FilmParserError::UnknownRule(format!(
-                                    "{:?}",
-                                    inner_pair_1.as_rule()
-                                )));
-                            }
-                        }
-                    }
-                }
-                _ => {
-                    return Err(FilmParserError::UnknownRule(format!(
-                        "{:?}",
-                        inner_pair.as_rule()
-                    )));
-                }
-            }
-        }
+    /// Builds a `Film` from a parsed `file` pair with the given options.
+    pub fn parse_to_struct_with(
+        pair: pest::iterators::Pair<Rule>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilmParserError> {
+        Self::parse_with_warnings(pair, options).map(|(film, _)| film)
+    }
 
-        if title.is_empty()
-            || year == 0
-            || director.is_empty()
-            || writer.is_empty()
-            || genre.is_empty()
-            || stars.is_empty()
-            || description.is_empty()
-        {
-            return Err(FilmParserError::MissingFieldsError);
-        }
+    /// Builds a `Film` from a parsed `file` pair and returns the problems tolerated in
+    /// lenient mode. In strict mode the warnings are always empty.
+    pub fn parse_with_warnings(
+        pair: pest::iterators::Pair<Rule>,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<WarningKind>), FilmParserError> {
+        FilmRef::parse_with_warnings(pair, options)
+            .map(|(film, warnings)| (film.to_owned(), warnings))
+    }
+}
 
-        Ok(Self::new(
-            title,
-            year,
-            director,
-            writer,
-            genre,
-            stars,
-            description,
-        ))
+/// A film data string to parse, with the place it was read from if it is known.
+pub trait RecordLine: Send {
+    /// Returns the text of the record and its location.
+    fn into_record(self) -> (String, Option<SourceLocation>);
+}
+
+impl RecordLine for String {
+    fn into_record(self) -> (String, Option<SourceLocation>) {
+        (self, None)
     }
 }
 
-/// Parses a list of film data strings into `Film` structs and writes results to files.
-pub fn parse_films(films: Vec<String>) -> Result<Vec<Film>, FilmParserError> {
-    let mut films_res = Vec::new();
+impl RecordLine for sources::SourceLine {
+    fn into_record(self) -> (String, Option<SourceLocation>) {
+        (self.text, Some(self.location))
+    }
+}
+
+/// Parses a list of film data strings into `Film` structs without writing any files.
+pub fn parse_film_lines<R: RecordLine>(films: Vec<R>) -> Result<Vec<Film>, FilmParserError> {
+    parse_film_lines_with(films, &ParseOptions::default())
+}
+
+/// Parses a list of film data strings into `Film` structs with the given options.
+pub fn parse_film_lines_with<R: RecordLine>(
+    films: Vec<R>,
+    options: &ParseOptions,
+) -> Result<Vec<Film>, FilmParserError> {
+    parse_film_lines_with_warnings(films, options).map(|(films, _)| films)
+}
 
-    for film in films {
-        let pairs = FilmParser::parse(Rule::file, &film)
-            .map_err(|_| FilmParserError::ParsingError(film.clone()))?;
-
-        for pair in pairs {
-            match Film::parse_to_struct(pair) {
-                Ok(parsed_film) => films_res.push(parsed_film),
-                Err(err) => {
-                    return Err(FilmParserError::ParsingError(format!(
-                        "{} - {:?}",
-                        film, err
-                    )))
-                }
-            }
+/// Parses a list of film data strings into `Film` structs with the given options and returns
+/// the problems tolerated in lenient mode. Records given as `sources::SourceLine`s set the
+/// source of their film and warnings, and their errors are wrapped in `FilmParserError::AtSource`;
+/// plain strings have no location.
+pub fn parse_film_lines_with_warnings<R: RecordLine>(
+    films: Vec<R>,
+    options: &ParseOptions,
+) -> Result<(Vec<Film>, Vec<ParseWarning>), FilmParserError> {
+    let parsed = parallel::map_records(films, options.jobs, |film| {
+        let (text, location) = film.into_record();
+        match parse_film_str(&text, options) {
+            Ok((film, kinds)) => Ok((film, kinds, location)),
+            Err(e) => Err(match location {
+                Some(location) => FilmParserError::AtSource(location, Box::new(e)),
+                None => e,
+            }),
         }
+    })?;
+
+    let mut films_res = Vec::new();
+    let mut warnings = Vec::new();
+    for (mut film, kinds, location) in parsed {
+        warnings.extend(kinds.into_iter().map(|kind| ParseWarning {
+            kind,
+            location: location.clone(),
+        }));
+        film.source = location;
+        films_res.push(film);
     }
+    Ok((films_res, warnings))
+}
+
+/// Parses one film data string with the given options, normalising its keys first
+/// if a key table is set.
+pub(crate) fn parse_film_str(
+    film: &str,
+    options: &ParseOptions,
+) -> Result<(Film, Vec<WarningKind>), FilmParserError> {
+    with_film_ref(film, options, |film| film.to_owned())
+}
+
+/// Parses one film data string like `parse_film_str` and passes the borrowed film to `f`.
+pub(crate) fn with_film_ref<R>(
+    film: &str,
+    options: &ParseOptions,
+    f: impl FnOnce(&FilmRef) -> R,
+) -> Result<(R, Vec<WarningKind>), FilmParserError> {
+    let film = match &options.keys {
+        Some(keys) => borrow::Cow::Owned(keys.normalise(film)),
+        None => borrow::Cow::Borrowed(film),
+    };
+    FilmRef::parse(&film, options).map(|(film, warnings)| (f(&film), warnings))
+}
 
-    write_films_to_file(films_res.clone(), "data/result_file.txt")?;
+/// Parses a list of film data strings into `Film` structs and writes results to files.
+/// `sources::SourceLine`s give every film its source, like in `parse_film_lines_with_warnings`.
+pub fn parse_films<R: RecordLine>(films: Vec<R>) -> Result<Vec<Film>, FilmParserError> {
+    let films_res = parse_film_lines(films)?;
+
+    write_films_to_file(films_res.clone(), RESULT_FILE)?;
     write_films_to_file_as_structure_without_formating(
         films_res.clone(),
-        "data/result_wo_formating_file.txt",
+        RESULT_WO_FORMATING_FILE,
     )?;
 
     Ok(films_res)
@@ -276,24 +356,50 @@ pub fn parse_films(films: Vec<String>) -> Result<Vec<Film>, FilmParserError> {
 
 /// Writes parsed data to specified file with formated string.
 pub fn write_films_to_file(films: Vec<Film>, filename: &str) -> Result<(), FilmParserError> {
-    let mut file = File::create(filename)
-        .map_err(|_| FilmParserError::FileCreatingError(filename.to_string()))?;
-    for film in films {
-        writeln!(
-            file,
-            "Title: {}\nYear: {}\nDirector: {}\nWriter: {}\nGenre: {}\nStars: {}\nDescription: {}\n",
-            film.title,
-            film.year,
-            film.director,
-            film.writer,
-            film.genre.join(", "),
-            film.stars.join(", "),
-            film.description
-        )
-            .map_err(|_| FilmParserError::FileWritingError(filename.to_string()))?;
-    }
+    write_films_with_fields(&films, &FilmField::ALL, filename)
+}
+
+/// Writes the selected fields of parsed data to specified file with formated string.
+pub fn write_films_with_fields(
+    films: &[Film],
+    fields: &[FilmField],
+    filename: &str,
+) -> Result<(), FilmParserError> {
+    write_lines(
+        filename,
+        films
+            .iter()
+            .map(|film| view::format_film_fields(film, fields)),
+    )
+}
+
+/// Writes films to specified file as record lines that can be parsed again.
+pub fn write_films_as_records(films: &[Film], filename: &str) -> Result<(), FilmParserError> {
+    write_lines(filename, films.iter().map(Film::to_record_line))
+}
+
+/// Writes films to specified file as JSON lines, one object per film.
+pub fn write_films_as_json_lines(films: &[Film], filename: &str) -> Result<(), FilmParserError> {
+    let lines = films
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(|e| FilmParserError::SerializationError(e.to_string()))?;
+    write_lines(filename, lines.into_iter())
+}
 
-    Ok(())
+/// Writes films as JSON lines if the file name ends with `.jsonl`, and as record lines
+/// otherwise. A `.gz` or `.zst` extension after that compresses the file, e.g. `out.jsonl.zst`.
+pub fn write_catalogue(films: &[Film], filename: &str) -> Result<(), FilmParserError> {
+    let path = compression::uncompressed_path(Path::new(filename));
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "jsonl")
+    {
+        write_films_as_json_lines(films, filename)
+    } else {
+        write_films_as_records(films, filename)
+    }
 }
 
 /// Writes parsed data to specified file without formating.
@@ -301,13 +407,15 @@ pub fn write_films_to_file_as_structure_without_formating(
     films: Vec<Film>,
     filename: &str,
 ) -> Result<(), FilmParserError> {
-    let mut file = File::create(filename)
-        .map_err(|_| FilmParserError::FileCreatingError(filename.to_string()))?;
+    write_lines(filename, films.iter().map(|film| format!("{:?}", film)))
+}
 
-    for film in films {
-        writeln!(file, "{:?}", film)
-            .map_err(|_| FilmParserError::FileWritingError(filename.to_string()))?;
+/// Writes one line per item, compressing the file if it ends with `.gz` or `.zst`.
+fn write_lines(filename: &str, lines: impl Iterator<Item = String>) -> Result<(), FilmParserError> {
+    let writing_error = |_| FilmParserError::FileWritingError(filename.to_string());
+    let mut file = compression::FileWriter::create(Path::new(filename))?;
+    for line in lines {
+        writeln!(file, "{}", line).map_err(writing_error)?;
     }
-
-    Ok(())
+    file.finish().map_err(writing_error)
 }
@@ -0,0 +1,371 @@
+//! Lint rules over parsed films.
+//!
+//! A record can parse and still be suspicious, e.g. a release year in the future or the same star
+//! listed twice. Every `LintRule` has a name and a `Severity`; a config file changes the severities
+//! with one `rule = severity` line per rule, where the severity is `off`, `warn` or `error`.
+//! Empty lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # Curated data must be clean.
+//! future-year = error
+//! description-punctuation = off
+//! ```
+
+use crate::{Film, FilmParserError, ParseWarning, SourceLocation, WarningKind};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Config file read by the `lint` command when no `--config` is given and the file exists.
+pub const DEFAULT_CONFIG_FILE: &str = "filmlint.conf";
+
+/// Titles longer than this many characters are reported by `LintRule::LongTitle`.
+pub const MAX_TITLE_LENGTH: usize = 100;
+
+/// Director values that stand for a missing director.
+const DIRECTOR_PLACEHOLDERS: [&str; 5] = ["unknown", "n/a", "na", "-", "?"];
+
+/// How a lint rule is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The rule is not checked.
+    Off,
+    /// Issues are reported.
+    Warning,
+    /// Issues are reported and make the `lint` command fail.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Off => "off",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+impl FromStr for Severity {
+    type Err = FilmParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "allow" => Ok(Severity::Off),
+            "warn" | "warning" => Ok(Severity::Warning),
+            "error" | "deny" => Ok(Severity::Error),
+            _ => Err(FilmParserError::InvalidOption(format!(
+                "severity {}, expected off, warn or error",
+                s
+            ))),
+        }
+    }
+}
+
+/// A check of a single film.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// The release year is after the current year.
+    FutureYear,
+    /// A genre or star is empty, e.g. from `Genre: [Drama, ]`. Lenient parsing drops such items
+    /// with a `WarningKind::EmptyListItem`, which `Linter::lint_with_warnings` reports here.
+    EmptyListItem,
+    /// The same star is listed twice, ignoring case.
+    DuplicateStar,
+    /// The description does not end with `.`, `!`, `?`, `…` or a closing quote.
+    DescriptionPunctuation,
+    /// The title is longer than `MAX_TITLE_LENGTH` characters.
+    LongTitle,
+    /// The director is blank or a placeholder such as `Unknown`.
+    UnnamedDirector,
+    /// The director is not credited in the record as its writer or one of its stars.
+    DirectorNotCredited,
+}
+
+impl LintRule {
+    /// All rules.
+    pub const ALL: [LintRule; 7] = [
+        LintRule::FutureYear,
+        LintRule::EmptyListItem,
+        LintRule::DuplicateStar,
+        LintRule::DescriptionPunctuation,
+        LintRule::LongTitle,
+        LintRule::UnnamedDirector,
+        LintRule::DirectorNotCredited,
+    ];
+
+    /// Returns the name of the rule used in config files and reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::FutureYear => "future-year",
+            LintRule::EmptyListItem => "empty-list-item",
+            LintRule::DuplicateStar => "duplicate-star",
+            LintRule::DescriptionPunctuation => "description-punctuation",
+            LintRule::LongTitle => "long-title",
+            LintRule::UnnamedDirector => "unnamed-director",
+            LintRule::DirectorNotCredited => "director-not-credited",
+        }
+    }
+
+    /// Returns the severity of the rule when the config does not set one.
+    pub fn default_severity(self) -> Severity {
+        match self {
+            LintRule::EmptyListItem | LintRule::UnnamedDirector => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+
+    /// Checks a film and returns a message for every issue found.
+    fn check(self, film: &Film, current_year: u32) -> Vec<String> {
+        match self {
+            LintRule::FutureYear if film.year > current_year => {
+                vec![format!("year {} is in the future", film.year)]
+            }
+            LintRule::EmptyListItem => [("genre", &film.genre), ("stars", &film.stars)]
+                .into_iter()
+                .flat_map(|(list, items)| {
+                    items
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, item)| item.trim().is_empty())
+                        .map(move |(index, _)| format!("{} item {} is empty", list, index + 1))
+                })
+                .collect(),
+            LintRule::DuplicateStar => {
+                let mut seen: Vec<String> = Vec::new();
+                let mut messages = Vec::new();
+                for star in &film.stars {
+                    let key = star.trim().to_lowercase();
+                    if key.is_empty() {
+                        continue;
+                    }
+                    if seen.contains(&key) {
+                        messages.push(format!("star {} is listed more than once", star.trim()));
+                    } else {
+                        seen.push(key);
+                    }
+                }
+                messages
+            }
+            LintRule::DescriptionPunctuation => {
+                let ends_sentence = film
+                    .description
+                    .trim_end()
+                    .ends_with(['.', '!', '?', '…', '"', '”', '»', ')']);
+                if ends_sentence {
+                    Vec::new()
+                } else {
+                    vec!["description does not end with punctuation".to_string()]
+                }
+            }
+            LintRule::LongTitle if film.title.trim().chars().count() > MAX_TITLE_LENGTH => {
+                vec![format!(
+                    "title is longer than {} characters",
+                    MAX_TITLE_LENGTH
+                )]
+            }
+            LintRule::UnnamedDirector if is_placeholder(&film.director) => {
+                vec!["director is blank or a placeholder".to_string()]
+            }
+            LintRule::DirectorNotCredited if !is_placeholder(&film.director) => {
+                let director = film.director.trim().to_lowercase();
+                let credited = std::iter::once(&film.writer)
+                    .chain(&film.stars)
+                    .any(|name| name.trim().to_lowercase() == director);
+                if credited {
+                    Vec::new()
+                } else {
+                    vec![format!(
+                        "director {} is not credited as the writer or a star",
+                        film.director.trim()
+                    )]
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for LintRule {
+    type Err = FilmParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        LintRule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == name)
+            .ok_or_else(|| FilmParserError::InvalidOption(format!("lint rule {}", s)))
+    }
+}
+
+/// Severities of the lint rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    severities: HashMap<LintRule, Severity>,
+}
+
+impl LintConfig {
+    /// Parses a config with one `rule = severity` line per rule.
+    pub fn parse(content: &str) -> Result<Self, FilmParserError> {
+        let mut config = LintConfig::default();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |e: FilmParserError| {
+                FilmParserError::ConfigError(format!("line {}: {}", index + 1, e))
+            };
+            let Some((rule, severity)) = line.split_once('=') else {
+                return Err(invalid(FilmParserError::InvalidOption(format!(
+                    "'{}', expected rule = severity",
+                    line
+                ))));
+            };
+            config.set(
+                rule.parse().map_err(invalid)?,
+                severity.parse().map_err(invalid)?,
+            );
+        }
+        Ok(config)
+    }
+
+    /// Reads a config file.
+    pub fn load(path: &Path) -> Result<Self, FilmParserError> {
+        let content = fs::read_to_string(path)
+            .map_err(|_| FilmParserError::FileOpeningError(path.display().to_string()))?;
+        Self::parse(&content).map_err(|e| match e {
+            FilmParserError::ConfigError(message) => {
+                FilmParserError::ConfigError(format!("{} {}", path.display(), message))
+            }
+            e => e,
+        })
+    }
+
+    /// Sets the severity of a rule.
+    pub fn set(&mut self, rule: LintRule, severity: Severity) {
+        self.severities.insert(rule, severity);
+    }
+
+    /// Returns the severity of a rule.
+    pub fn severity(&self, rule: LintRule) -> Severity {
+        self.severities
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+}
+
+/// A problem found by a lint rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// The rule that found the problem.
+    pub rule: LintRule,
+    /// The configured severity of the rule.
+    pub severity: Severity,
+    /// Position of the film in the linted list.
+    pub film: usize,
+    /// Where the film was read from, if known.
+    pub source: Option<SourceLocation>,
+    /// What is wrong.
+    pub message: String,
+}
+
+/// Checks films against the enabled lint rules.
+#[derive(Debug, Clone)]
+pub struct Linter {
+    config: LintConfig,
+    current_year: u32,
+}
+
+impl Linter {
+    /// Creates a linter with the given severities, taking the current year from the system clock.
+    pub fn new(config: LintConfig) -> Self {
+        Linter {
+            config,
+            current_year: current_year(),
+        }
+    }
+
+    /// Sets the year after which release years are reported by `LintRule::FutureYear`.
+    pub fn with_current_year(mut self, year: u32) -> Self {
+        self.current_year = year;
+        self
+    }
+
+    /// Returns the issues of all films, in film and rule order.
+    pub fn lint(&self, films: &[Film]) -> Vec<LintIssue> {
+        self.lint_with_warnings(films, &[])
+    }
+
+    /// Returns the issues of all films like `lint`, and also reports the empty list items that
+    /// lenient parsing dropped from the films with a `WarningKind::EmptyListItem` warning as
+    /// `LintRule::EmptyListItem` issues. Warnings are matched to films by their source.
+    pub fn lint_with_warnings(&self, films: &[Film], warnings: &[ParseWarning]) -> Vec<LintIssue> {
+        let mut issues = Vec::new();
+        for (index, film) in films.iter().enumerate() {
+            for rule in LintRule::ALL {
+                let severity = self.config.severity(rule);
+                if severity == Severity::Off {
+                    continue;
+                }
+                let mut messages = rule.check(film, self.current_year);
+                if rule == LintRule::EmptyListItem && film.source.is_some() {
+                    messages.extend(
+                        warnings
+                            .iter()
+                            .filter(|warning| warning.location == film.source)
+                            .filter_map(|warning| match &warning.kind {
+                                WarningKind::EmptyListItem(list, item) => {
+                                    Some(format!("{} item {} is empty", list, item))
+                                }
+                                _ => None,
+                            }),
+                    );
+                }
+                for message in messages {
+                    issues.push(LintIssue {
+                        rule,
+                        severity,
+                        film: index,
+                        source: film.source.clone(),
+                        message,
+                    });
+                }
+            }
+        }
+        issues
+    }
+}
+
+/// Returns `true` if the director is blank or one of `DIRECTOR_PLACEHOLDERS`.
+fn is_placeholder(director: &str) -> bool {
+    let director = director.trim().to_lowercase();
+    director.is_empty() || DIRECTOR_PLACEHOLDERS.contains(&director.as_str())
+}
+
+/// Returns the current year in UTC.
+pub fn current_year() -> u32 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    // Civil date from days since 1970-01-01, after Howard Hinnant's `civil_from_days`.
+    let days = seconds / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400 + u64::from(month >= 10);
+    year as u32
+}
@@ -1,14 +1,32 @@
 use colored::*;
+use film_parser::messages::Language;
+use film_parser::options::KNOWN_GENRES;
 use film_parser::*;
+use std::collections::HashMap;
+use std::fmt;
 use std::process::Command;
+use std::sync::OnceLock;
+
+/// Language of the messages, set once from `--lang` or the locale.
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
 
 fn main() -> anyhow::Result<()> {
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+    match take_language(&mut args) {
+        Ok(language) => {
+            LANGUAGE.get_or_init(|| language);
+        }
+        Err(e) => {
+            eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+            return Ok(());
+        }
+    }
 
     if args.len() < 2 {
         eprintln!(
-            "{}: No command provided. Use 'cargo run -- help' for usage information.",
-            "Error".red().bold()
+            "{}: {}",
+            tr("Error").red().bold(),
+            tr("No command provided. Use 'cargo run -- help' for usage information.")
         );
         return Ok(());
     }
@@ -24,66 +42,1512 @@ fn main() -> anyhow::Result<()> {
 
             if !output.status.success() {
                 eprintln!(
-                    "Tests failed: {}",
+                    "{} {}",
+                    tr("Tests failed:"),
                     String::from_utf8_lossy(&output.stderr).red().bold()
                 );
             } else {
                 println!(
-                    "Tests passed successfully:\n{}",
+                    "{}\n{}",
+                    tr("Tests passed successfully:"),
                     String::from_utf8_lossy(&output.stdout).green().bold()
                 );
             }
         }
         "parse" => {
-            if args.len() <= 2 {
-                eprintln!("{}: No file path provided. You must specify path to the file with information.", "Error".red().bold());
+            let command_args = CommandArgs::parse(&args[2..]);
+            if command_args.positional.is_empty() {
+                eprintln!("{}: {}", tr("Error").red().bold(), tr("No file path provided. You must specify path to the file with information."));
+                return Ok(());
+            }
+            let Some(view_options) = ViewOptions::from_args(&command_args) else {
+                return Ok(());
+            };
+            if view_options.group_by.is_some() {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("--group-by can only be used with filter and search.")
+                );
+                return Ok(());
+            }
+            if command_args.option("--output").is_some()
+                && command_args.option("--fields").is_some()
+            {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("--fields cannot be used with --output, which writes whole records.")
+                );
                 return Ok(());
             }
-            let filepath = &args[2];
-            match read_lines(filepath) {
-                Ok(lines) => {
-                    parse_films(lines)?;
+            let policy = match command_args.option("--policy") {
+                Some(_) => match merge_policy(&command_args) {
+                    Some(policy) => Some(policy),
+                    None => return Ok(()),
+                },
+                None => None,
+            };
+            match sources::load_catalogue(
+                &command_args.positional,
+                policy,
+                &parse_options(&command_args),
+            ) {
+                Ok((catalogue, warnings)) => {
+                    print_warnings(&warnings);
+                    let mut films = catalogue.films;
+                    if !catalogue.merged.is_empty() {
+                        println!(
+                            "{}",
+                            trf(
+                                "Merged {} duplicated films, {} films left.",
+                                &[&catalogue.merged.len(), &films.len()]
+                            )
+                            .green()
+                            .bold()
+                        );
+                    }
+                    view::sort_films(&mut films, &view_options.sort_keys);
+                    if let Some(output) = command_args.option("--output") {
+                        write_catalogue(&films, output)?;
+                        println!(
+                            "{}",
+                            trf("Wrote {} films to {}.", &[&films.len(), &output])
+                                .green()
+                                .bold()
+                        );
+                    } else {
+                        write_films_with_fields(&films, &view_options.fields, RESULT_FILE)?;
+                        write_films_to_file_as_structure_without_formating(
+                            films,
+                            RESULT_WO_FORMATING_FILE,
+                        )?;
+                    }
                 }
                 Err(e) => eprintln!(
                     "{}'{}': {}",
-                    "Error reading file ".red().bold(),
-                    filepath,
-                    e
+                    tr("Error reading files ").red().bold(),
+                    command_args.positional.join(" "),
+                    error_text(&e)
                 ),
             }
         }
+        "filter" | "search" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            if command_args.positional.len() < 2 {
+                eprintln!("{}: {}", tr("Error").red().bold(), tr("You must specify path to the file and a query, e.g. 'year >= 2015 and genre = Horror'."));
+                return Ok(());
+            }
+            let Some(view_options) = ViewOptions::from_args(&command_args) else {
+                return Ok(());
+            };
+            let query = match Query::parse(&command_args.positional[1..].join(" ")) {
+                Ok(query) => query,
+                Err(e) => {
+                    eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+                    return Ok(());
+                }
+            };
+            let mut scanned = 0;
+            if let Some(mut matched) = scan_films(
+                &command_args.positional[0],
+                &parse_options(&command_args),
+                |film| {
+                    scanned += 1;
+                    query.matches_ref(film)
+                },
+            ) {
+                view::sort_films(&mut matched, &view_options.sort_keys);
+                print_films(&matched, &view_options);
+                println!(
+                    "{}",
+                    trf("Found {} of {} films.", &[&matched.len(), &scanned])
+                        .green()
+                        .bold()
+                );
+            }
+        }
+        "stats" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            let Some(filepath) = command_args.positional.first() else {
+                eprintln!("{}: {}", tr("Error").red().bold(), tr("No file path provided. You must specify path to the file with information."));
+                return Ok(());
+            };
+            let Some(top) = number_option(&command_args, "--top", stats::DEFAULT_TOP) else {
+                return Ok(());
+            };
+            if let Some(films) = load_films(filepath, &parse_options(&command_args)) {
+                let catalogue_stats = CatalogueStats::compute_with_top(&films, top);
+                if command_args.options.contains_key("--json") {
+                    println!("{}", catalogue_stats.to_json()?);
+                } else {
+                    print_stats(&catalogue_stats);
+                }
+            }
+        }
+        "chart" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            let Some(filepath) = command_args.positional.first() else {
+                eprintln!("{}: {}", tr("Error").red().bold(), tr("No file path provided. You must specify path to the file with information."));
+                return Ok(());
+            };
+            let kind = command_args
+                .positional
+                .get(1)
+                .map(String::as_str)
+                .unwrap_or("all");
+            let (Some(top), Some(width), Some(height)) = (
+                number_option(&command_args, "--top", stats::DEFAULT_TOP),
+                number_option(&command_args, "--width", chart::terminal_width()),
+                number_option(&command_args, "--height", chart::DEFAULT_HEIGHT),
+            ) else {
+                return Ok(());
+            };
+            if let Some(films) = load_films(filepath, &parse_options(&command_args)) {
+                let catalogue_stats = CatalogueStats::compute_with_top(&films, top);
+                let charts = [
+                    ("years", tr("Films per year"), &catalogue_stats.years, true),
+                    (
+                        "decades",
+                        tr("Films per decade"),
+                        &catalogue_stats.decades,
+                        true,
+                    ),
+                    ("genres", tr("Genres"), &catalogue_stats.genres, false),
+                    (
+                        "directors",
+                        tr("Top directors"),
+                        &catalogue_stats.top_directors,
+                        false,
+                    ),
+                    ("stars", tr("Top stars"), &catalogue_stats.top_stars, false),
+                    (
+                        "descriptions",
+                        tr("Description lengths"),
+                        &catalogue_stats.description_lengths.buckets,
+                        true,
+                    ),
+                ];
+                if kind != "all" && !charts.iter().any(|(name, ..)| *name == kind) {
+                    eprintln!("{}: {}", tr("Error").red().bold(), trf("Unknown chart '{}'. Expected years, decades, genres, directors, stars, descriptions or all.", &[&kind]));
+                    return Ok(());
+                }
+                for (name, title, rows, is_histogram) in charts {
+                    if kind != "all" && kind != name {
+                        continue;
+                    }
+                    if is_histogram {
+                        println!("{}", chart::render_histogram(title, rows, height, width));
+                    } else {
+                        println!("{}", chart::render_bar_chart(title, rows, width));
+                    }
+                }
+            }
+        }
+        "graph" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            let positional = &command_args.positional;
+            let query = positional.get(1).map(String::as_str);
+            let arguments_ok = match query {
+                Some("collaborators") | Some("export") => positional.len() == 3,
+                Some("path") => positional.len() == 4,
+                _ => false,
+            };
+            if !arguments_ok {
+                eprintln!("{}: {}", tr("Error").red().bold(), tr("Expected 'graph <filename> collaborators <person>', 'graph <filename> path <person> <person>' or 'graph <filename> export dot|graphml'."));
+                return Ok(());
+            }
+            let role = match command_args.option("--role").map(str::parse).transpose() {
+                Ok(role) => role,
+                Err(e) => {
+                    eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+                    return Ok(());
+                }
+            };
+            let Some(top) = number_option(&command_args, "--top", stats::DEFAULT_TOP) else {
+                return Ok(());
+            };
+            if let Some(films) = load_films(&positional[0], &parse_options(&command_args)) {
+                let graph = CollaborationGraph::build(&films);
+                let result = if query == Some("export") {
+                    export_graph(&graph, &positional[2], &command_args)
+                } else if query == Some("collaborators") {
+                    graph
+                        .collaborators(&positional[2], role)
+                        .map(|collaborators| print_collaborators(&collaborators, top))
+                } else {
+                    graph
+                        .shortest_path(&positional[2], &positional[3])
+                        .map(|path| print_path(path.as_deref()))
+                };
+                if let Err(e) = result {
+                    print_lookup_error(&e, &films);
+                }
+            }
+        }
+        "similar" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            if command_args.positional.len() < 2 {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("You must specify path to the file and a film title.")
+                );
+                return Ok(());
+            }
+            let Some(top) = number_option(&command_args, "--top", stats::DEFAULT_TOP) else {
+                return Ok(());
+            };
+            if let Some(films) =
+                load_films(&command_args.positional[0], &parse_options(&command_args))
+            {
+                let title = command_args.positional[1..].join(" ");
+                match recommend::Recommender::new(&films).similar(&title, top) {
+                    Ok(recommendations) => print_recommendations(&recommendations),
+                    Err(e) => print_lookup_error(&e, &films),
+                }
+            }
+        }
+        "find" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            if command_args.positional.len() < 2 {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("You must specify path to the file and the words to search for.")
+                );
+                return Ok(());
+            }
+            let Some(top) = number_option(&command_args, "--top", stats::DEFAULT_TOP) else {
+                return Ok(());
+            };
+            let filepath = &command_args.positional[0];
+            match index::SearchIndex::open_or_build(filepath, &parse_options(&command_args)) {
+                Ok(opened) => {
+                    print_warnings(&opened.warnings);
+                    if let Some(e) = &opened.save_error {
+                        eprintln!(
+                            "{}: {}",
+                            tr("Warning").yellow().bold(),
+                            trf("The index was not saved: {}", &[&error_text(e)])
+                        );
+                    }
+                    let search_index = opened.index;
+                    if opened.rebuilt {
+                        println!(
+                            "{}",
+                            trf("Indexed {} films.", &[&search_index.len()]).italic()
+                        );
+                    }
+                    let hits = search_index.search(&command_args.positional[1..].join(" "), top);
+                    for hit in &hits {
+                        println!(
+                            "{} {} {}",
+                            hit.title.yellow().bold(),
+                            format!("({})", hit.year).italic(),
+                            format!("{:.3}", hit.score).green()
+                        );
+                    }
+                    if hits.is_empty() {
+                        println!("{}", tr("No films found.").italic());
+                    }
+                }
+                Err(e) => eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e)),
+            }
+        }
+        "lookup" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            if command_args.positional.len() < 2 {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("You must specify path to the file and a title or name.")
+                );
+                return Ok(());
+            }
+            let Some(top) = number_option(&command_args, "--top", stats::DEFAULT_TOP) else {
+                return Ok(());
+            };
+            if let Some(films) =
+                load_films(&command_args.positional[0], &parse_options(&command_args))
+            {
+                let matcher = fuzzy::FuzzyMatcher::new(&films);
+                let matches = matcher.lookup(&command_args.positional[1..].join(" "), None, top);
+                for found in &matches {
+                    let kind = match found.kind {
+                        fuzzy::MatchKind::Title => tr("title"),
+                        fuzzy::MatchKind::Person => tr("person"),
+                    };
+                    println!(
+                        "{} {} {}",
+                        found.text.yellow().bold(),
+                        format!("({})", kind).italic(),
+                        format!("{:.3}", found.score).green()
+                    );
+                }
+                if matches.is_empty() {
+                    println!("{}", tr("No similar titles or names found.").italic());
+                }
+            }
+        }
+        "dedupe" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            let Some(filepath) = command_args.positional.first() else {
+                eprintln!("{}: {}", tr("Error").red().bold(), tr("No file path provided. You must specify path to the file with information."));
+                return Ok(());
+            };
+            let Some(policy) = merge_policy(&command_args) else {
+                return Ok(());
+            };
+            if let Some(films) = load_films(filepath, &parse_options(&command_args)) {
+                if command_args.options.contains_key("--merge") {
+                    let deduplicated = dedupe::dedupe(&films, policy);
+                    print_merged(&deduplicated.merged, &films);
+                    if let Some(output) = command_args.option("--output") {
+                        write_catalogue(&deduplicated.films, output)?;
+                        println!(
+                            "{}",
+                            trf(
+                                "Wrote {} films to {}.",
+                                &[&deduplicated.films.len(), &output]
+                            )
+                            .green()
+                            .bold()
+                        );
+                    }
+                } else {
+                    print_duplicates(&dedupe::find_duplicates(&films), &films);
+                }
+            }
+        }
+        "diff" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            let [old_path, new_path] = command_args.positional.as_slice() else {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("You must specify the paths to the old and the new file.")
+                );
+                return Ok(());
+            };
+            let options = parse_options(&command_args);
+            if let (Some(old), Some(new)) = (
+                load_films(old_path, &options),
+                load_films(new_path, &options),
+            ) {
+                let catalogue_diff = diff::CatalogueDiff::compute(&old, &new);
+                if command_args.options.contains_key("--json") {
+                    println!("{}", catalogue_diff.to_json()?);
+                } else {
+                    print_diff(&catalogue_diff);
+                }
+            }
+        }
+        "fmt" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            if command_args.positional.is_empty() {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("You must specify the files to format.")
+                );
+                return Ok(());
+            }
+            let sort_keys = match command_args.option("--sort-by") {
+                Some(keys) => view::SortKey::parse_list(keys),
+                None => Ok(Vec::new()),
+            };
+            let language = match command_args.option("--key-lang") {
+                Some(code) => key_table(&command_args)
+                    .unwrap_or_else(keys::KeyTable::builtin)
+                    .language(code)
+                    .cloned()
+                    .map(Some),
+                None => Ok(None),
+            };
+            let options = match (sort_keys, language) {
+                (Ok(sort_keys), Ok(keys)) => format::FormatOptions {
+                    sort_keys,
+                    parse: parse_options(&command_args),
+                    keys,
+                },
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+                    return Ok(());
+                }
+            };
+            let check = command_args.options.contains_key("--check");
+            if !format_files(&command_args.positional, &options, check) {
+                std::process::exit(1);
+            }
+        }
+        "lint" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            if command_args.positional.is_empty() {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("You must specify the files to check.")
+                );
+                return Ok(());
+            }
+            let config_path = command_args.option("--config").map(std::path::Path::new);
+            let config = match config_path {
+                Some(path) => lint::LintConfig::load(path),
+                None if std::path::Path::new(lint::DEFAULT_CONFIG_FILE).exists() => {
+                    lint::LintConfig::load(std::path::Path::new(lint::DEFAULT_CONFIG_FILE))
+                }
+                None => Ok(lint::LintConfig::default()),
+            };
+            // Records are read leniently so that empty list items reach the linter; in strict mode
+            // the other repairs are still reported as errors.
+            let options = parse_options(&command_args);
+            let strict = !options.is_lenient();
+            let lenient = ParseOptions {
+                mode: ParseMode::Lenient,
+                ..options
+            };
+            let films = config.and_then(|config| {
+                sources::expand_inputs(&command_args.positional)
+                    .and_then(|files| sources::load_files(&files, &lenient))
+                    .map(|films| (config, films))
+            });
+            match films {
+                Ok((config, (films, warnings))) => {
+                    let repairs: Vec<ParseWarning> = warnings
+                        .iter()
+                        .filter(|warning| !matches!(warning.kind, WarningKind::EmptyListItem(..)))
+                        .cloned()
+                        .collect();
+                    if strict {
+                        for repair in &repairs {
+                            let error = FilmParserError::StrictModeError(repair.kind.clone());
+                            let error = match &repair.location {
+                                Some(location) => {
+                                    FilmParserError::AtSource(location.clone(), Box::new(error))
+                                }
+                                None => error,
+                            };
+                            eprintln!("{}: {}", tr("Error").red().bold(), error_text(&error));
+                        }
+                    } else {
+                        print_warnings(&repairs);
+                    }
+                    let issues = lint::Linter::new(config).lint_with_warnings(&films, &warnings);
+                    print_lint_issues(&issues, &films);
+                    let lint_errors = issues
+                        .iter()
+                        .any(|issue| issue.severity == lint::Severity::Error);
+                    if lint_errors || (strict && !repairs.is_empty()) {
+                        std::process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+                    std::process::exit(1);
+                }
+            }
+        }
+        "generate" => {
+            let command_args = CommandArgs::parse(&args[2..]);
+            let count = command_args
+                .positional
+                .first()
+                .and_then(|count| count.parse::<usize>().ok());
+            let (Some(count), Some(output)) = (count, command_args.positional.get(1)) else {
+                eprintln!(
+                    "{}: {}",
+                    tr("Error").red().bold(),
+                    tr("You must specify the number of films and the output file.")
+                );
+                return Ok(());
+            };
+            let defaults = synthetic::SyntheticCatalogue::default();
+            let (Some(cast), Some(seed)) = (
+                number_option(&command_args, "--cast", defaults.cast),
+                number_option(&command_args, "--seed", defaults.seed as usize),
+            ) else {
+                return Ok(());
+            };
+            let films = synthetic::SyntheticCatalogue::new(count)
+                .with_cast(cast)
+                .with_seed(seed as u64)
+                .generate();
+            write_catalogue(&films, output)?;
+            println!(
+                "{}",
+                trf("Wrote {} films to {}.", &[&films.len(), &output])
+                    .green()
+                    .bold()
+            );
+        }
         _ => eprintln!(
-            "{}: Invalid command. Use 'cargo run -- help' for usage information.",
-            "Error".red().bold()
+            "{}: {}",
+            tr("Error").red().bold(),
+            tr("Invalid command. Use 'cargo run -- help' for usage information.")
         ),
     }
     Ok(())
 }
 
-fn show_help() {
+/// Options that take a value; any other `--name` argument is a flag.
+const VALUE_OPTIONS: [&str; 18] = [
+    "--sort-by",
+    "--group-by",
+    "--fields",
+    "--top",
+    "--width",
+    "--height",
+    "--role",
+    "--nodes",
+    "--min-shared",
+    "--output",
+    "--policy",
+    "--config",
+    "--keys",
+    "--key-lang",
+    "--encoding",
+    "--jobs",
+    "--cast",
+    "--seed",
+];
+
+/// Removes `--lang <code>` or `--lang=<code>` from the arguments and returns that language,
+/// or the language of the locale if the option is not given.
+fn take_language(args: &mut Vec<String>) -> Result<Language, FilmParserError> {
+    let Some(index) = args
+        .iter()
+        .position(|arg| arg == "--lang" || arg.starts_with("--lang="))
+    else {
+        return Ok(Language::from_env());
+    };
+    let arg = args.remove(index);
+    let code = match arg.strip_prefix("--lang=") {
+        Some(code) => code.to_string(),
+        None if index < args.len() => args.remove(index),
+        None => String::new(),
+    };
+    code.parse()
+}
+
+/// Returns the message in the selected language.
+fn tr(message: &'static str) -> &'static str {
+    language().translate(message)
+}
+
+/// Returns the message in the selected language with its `{}` placeholders filled in.
+fn trf(message: &'static str, args: &[&dyn fmt::Display]) -> String {
+    language().format(message, args)
+}
+
+/// Returns the message of the error in the selected language.
+fn error_text(error: &FilmParserError) -> String {
+    language().error(error)
+}
+
+fn language() -> Language {
+    LANGUAGE.get().copied().unwrap_or_default()
+}
+
+/// Short options and the long options they stand for.
+const SHORT_OPTIONS: [(&str, &str); 1] = [("-o", "--output")];
+
+/// Command arguments split into positional arguments and `--name [value]` options.
+struct CommandArgs {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl CommandArgs {
+    fn parse(args: &[String]) -> Self {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let arg = SHORT_OPTIONS
+                .iter()
+                .find(|(short, _)| short == arg)
+                .map_or(arg.as_str(), |(_, long)| long);
+            if let Some((name, value)) = arg.split_once('=').filter(|_| arg.starts_with("--")) {
+                options.insert(name.to_string(), value.to_string());
+            } else if VALUE_OPTIONS.contains(&arg) {
+                options.insert(arg.to_string(), iter.next().cloned().unwrap_or_default());
+            } else if arg.starts_with("--") {
+                options.insert(arg.to_string(), String::new());
+            } else {
+                positional.push(arg.to_string());
+            }
+        }
+        CommandArgs {
+            positional,
+            options,
+        }
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+}
+
+/// Reads a numeric option, printing an error if it is not a number.
+fn number_option(command_args: &CommandArgs, name: &str, default: usize) -> Option<usize> {
+    match command_args.option(name).map(str::parse::<usize>) {
+        None => Some(default),
+        Some(Ok(value)) => Some(value),
+        Some(Err(_)) => {
+            eprintln!(
+                "{}: {}",
+                tr("Error").red().bold(),
+                trf("{} must be a positive number.", &[&name])
+            );
+            None
+        }
+    }
+}
+
+/// Formats the files in place, or with `check` only reports the files that would change.
+/// Returns `false` if a file could not be formatted or, with `check`, would change.
+fn format_files(inputs: &[String], options: &format::FormatOptions, check: bool) -> bool {
+    let files = match sources::expand_inputs(inputs) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+            return false;
+        }
+    };
+    let mut success = true;
+    for file in &files {
+        let formatted = match format::format_file(file, options) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+                success = false;
+                continue;
+            }
+        };
+        print_warnings(&formatted.warnings);
+        if !formatted.changed {
+            continue;
+        }
+        if check {
+            println!(
+                "{} {}",
+                tr("Would reformat").yellow().bold(),
+                file.display()
+            );
+            success = false;
+        } else if compression::write_file(file, formatted.content.as_bytes()).is_ok() {
+            println!("{} {}", tr("Formatted").green().bold(), file.display());
+        } else {
+            eprintln!(
+                "{}: {}",
+                tr("Error").red().bold(),
+                error_text(&FilmParserError::FileWritingError(
+                    file.display().to_string()
+                ))
+            );
+            success = false;
+        }
+    }
+    success
+}
+
+/// Reads the `--lenient`, `--check-genres`, `--keys`, `--encoding` and `--jobs` options.
+fn parse_options(command_args: &CommandArgs) -> ParseOptions {
+    let options = if command_args.options.contains_key("--lenient") {
+        ParseOptions::lenient()
+    } else {
+        ParseOptions::strict()
+    };
+    let options = if command_args.options.contains_key("--check-genres") {
+        options.with_known_genres(&KNOWN_GENRES)
+    } else {
+        options
+    };
+    let options = match key_table(command_args) {
+        Some(keys) => options.with_key_table(keys),
+        None => options,
+    };
+    let options = match command_args
+        .option("--encoding")
+        .map(encoding::encoding_for_label)
+    {
+        Some(Ok(encoding)) => options.with_encoding(encoding),
+        Some(Err(e)) => {
+            eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+            std::process::exit(1);
+        }
+        None => options,
+    };
+    let Some(jobs) = number_option(command_args, "--jobs", 0) else {
+        std::process::exit(1);
+    };
+    if jobs > 1 && !cfg!(feature = "parallel") {
+        eprintln!(
+            "{}: {}",
+            tr("Warning").yellow().bold(),
+            tr("Built without the parallel feature, parsing on one thread.")
+        );
+    }
+    options.with_jobs(jobs)
+}
+
+/// Reads `--keys`, which is `builtin` or a key table file, exiting if the file is invalid.
+fn key_table(command_args: &CommandArgs) -> Option<keys::KeyTable> {
+    match command_args.option("--keys")? {
+        "builtin" => Some(keys::KeyTable::builtin()),
+        path => match keys::KeyTable::load(std::path::Path::new(path)) {
+            Ok(table) => Some(table),
+            Err(e) => {
+                eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// Prints the problems tolerated in lenient mode to stderr.
+fn print_warnings(warnings: &[ParseWarning]) {
+    for warning in warnings {
+        eprintln!(
+            "{}: {}",
+            tr("Warning").yellow().bold(),
+            language().warning(warning)
+        );
+    }
+}
+
+/// Reads `--policy`, printing an error if it is invalid.
+fn merge_policy(command_args: &CommandArgs) -> Option<dedupe::MergePolicy> {
+    match command_args.option("--policy") {
+        Some(policy) => dedupe::MergePolicy::parse_list(policy)
+            .map_err(|e| eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e)))
+            .ok(),
+        None => Some(dedupe::MergePolicy::default()),
+    }
+}
+
+/// How a list of films is ordered, grouped and which fields are shown.
+struct ViewOptions {
+    sort_keys: Vec<view::SortKey>,
+    group_by: Option<view::GroupBy>,
+    fields: Vec<FilmField>,
+}
+
+impl ViewOptions {
+    /// Reads `--sort-by`, `--group-by` and `--fields`, printing an error if any is invalid.
+    fn from_args(command_args: &CommandArgs) -> Option<Self> {
+        Self::try_from_args(command_args)
+            .map_err(|e| eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e)))
+            .ok()
+    }
+
+    fn try_from_args(command_args: &CommandArgs) -> Result<Self, FilmParserError> {
+        Ok(ViewOptions {
+            sort_keys: match command_args.option("--sort-by") {
+                Some(keys) => view::SortKey::parse_list(keys)?,
+                None => Vec::new(),
+            },
+            group_by: command_args
+                .option("--group-by")
+                .map(str::parse)
+                .transpose()?,
+            fields: match command_args.option("--fields") {
+                Some(fields) => view::parse_field_list(fields)?,
+                None => FilmField::ALL.to_vec(),
+            },
+        })
+    }
+}
+
+/// Reads and parses the films from the file, printing an error if this fails.
+fn load_films(filepath: &str, options: &ParseOptions) -> Option<Vec<Film>> {
+    loaded_films(
+        filepath,
+        sources::parse_file(std::path::Path::new(filepath), options),
+    )
+}
+
+/// Reads a file and keeps only the films for which `keep` returns `true`, copying nothing else.
+fn scan_films(
+    filepath: &str,
+    options: &ParseOptions,
+    keep: impl FnMut(&FilmRef) -> bool,
+) -> Option<Vec<Film>> {
+    loaded_films(
+        filepath,
+        sources::scan_file(std::path::Path::new(filepath), options, keep),
+    )
+}
+
+/// Prints the warnings of a loaded file and returns its films, or prints the error.
+fn loaded_films(
+    filepath: &str,
+    loaded: Result<(Vec<Film>, Vec<ParseWarning>), FilmParserError>,
+) -> Option<Vec<Film>> {
+    match loaded {
+        Ok((films, warnings)) => {
+            print_warnings(&warnings);
+            Some(films)
+        }
+        Err(e @ FilmParserError::AtSource(..)) => {
+            eprintln!("{}: {}", tr("Error").red().bold(), error_text(&e));
+            None
+        }
+        Err(e) => {
+            eprintln!(
+                "{}'{}': {}",
+                tr("Error reading file ").red().bold(),
+                filepath,
+                error_text(&e)
+            );
+            None
+        }
+    }
+}
+
+fn print_films(films: &[Film], view_options: &ViewOptions) {
+    match view_options.group_by {
+        Some(group_by) => {
+            for (key, group) in view::group_films(films, group_by) {
+                println!(
+                    "{} {}\n",
+                    "==".green().bold(),
+                    format!("{} ({})", key, group.len()).green().bold()
+                );
+                for film in group {
+                    print_film(film, &view_options.fields);
+                }
+            }
+        }
+        None => {
+            for film in films {
+                print_film(film, &view_options.fields);
+            }
+        }
+    }
+}
+
+fn print_film(film: &Film, fields: &[FilmField]) {
+    for field in fields {
+        let value = field.values(film).join(", ");
+        let value = if *field == FilmField::Title {
+            value.yellow().bold()
+        } else {
+            value.normal()
+        };
+        println!("{} {}", format!("{}:", field.key()).bold(), value);
+    }
+    println!();
+}
+
+fn print_stats(catalogue_stats: &CatalogueStats) {
     println!(
-        "{}",
-        "\nFilm Parser - A command-line tool for parsing film information from file."
+        "{} {}",
+        tr("Films:").green().bold(),
+        catalogue_stats.film_count.to_string().yellow().bold()
+    );
+    println!(
+        "{} {:.1}",
+        tr("Average cast size:").green().bold(),
+        catalogue_stats.average_cast_size
+    );
+    println!(
+        "{} {:.1}%",
+        tr("Directed and written by the same person:")
+            .green()
+            .bold(),
+        catalogue_stats.director_writer_share * 100.0
+    );
+    let lengths = &catalogue_stats.description_lengths;
+    println!(
+        "{} {}",
+        tr("Description length:").green().bold(),
+        trf(
+            "min {}, max {}, mean {}, median {}",
+            &[
+                &lengths.min,
+                &lengths.max,
+                &format!("{:.1}", lengths.mean),
+                &format!("{:.1}", lengths.median)
+            ]
+        )
+    );
+
+    print_table(tr("Genres"), &catalogue_stats.genres);
+    print_table(tr("Decades"), &catalogue_stats.decades);
+    print_table(tr("Years"), &catalogue_stats.years);
+    print_table(tr("Top directors"), &catalogue_stats.top_directors);
+    print_table(tr("Top writers"), &catalogue_stats.top_writers);
+    print_table(tr("Top stars"), &catalogue_stats.top_stars);
+    print_table(tr("Description lengths"), &lengths.buckets);
+}
+
+fn print_table(title: &str, rows: &[stats::Count]) {
+    println!("\n{}", title.green().bold());
+    let width = rows
+        .iter()
+        .map(|row| row.key.chars().count())
+        .max()
+        .unwrap_or(0);
+    for row in rows {
+        let padding = " ".repeat(width - row.key.chars().count());
+        println!(
+            "\t{}{}  {}",
+            row.key.italic(),
+            padding,
+            row.count.to_string().yellow()
+        );
+    }
+}
+
+/// Writes the graph as DOT or GraphML to `--output`, or prints it.
+fn export_graph(
+    graph: &CollaborationGraph,
+    format: &str,
+    command_args: &CommandArgs,
+) -> Result<(), FilmParserError> {
+    let format: export::ExportFormat = format.parse()?;
+    let mut options = export::ExportOptions::default();
+    if let Some(nodes) = command_args.option("--nodes") {
+        options.node_types = export::NodeType::parse_list(nodes)?;
+    }
+    if let Some(min_shared) = command_args.option("--min-shared") {
+        options.min_shared_films = min_shared
+            .parse()
+            .map_err(|_| FilmParserError::InvalidOption(format!("min-shared {}", min_shared)))?;
+    }
+
+    let exported = export::export(graph, format, &options);
+    match command_args.option("--output") {
+        Some(filename) => {
+            compression::write_file(std::path::Path::new(filename), exported.as_bytes())
+        }
+        None => {
+            print!("{}", exported);
+            Ok(())
+        }
+    }
+}
+
+/// Prints a failed title or name lookup with a "did you mean" suggestion.
+fn print_lookup_error(error: &FilmParserError, films: &[Film]) {
+    eprintln!("{}: {}", tr("Error").red().bold(), error_text(error));
+    let suggestion = match error {
+        FilmParserError::FilmNotFound(title) => {
+            fuzzy::FuzzyMatcher::new(films).suggest(title, fuzzy::MatchKind::Title)
+        }
+        FilmParserError::PersonNotFound(name) => {
+            fuzzy::FuzzyMatcher::new(films).suggest(name, fuzzy::MatchKind::Person)
+        }
+        _ => None,
+    };
+    if let Some(suggestion) = suggestion {
+        eprintln!(
+            "{}",
+            trf("Did you mean '{}'?", &[&suggestion.yellow().bold()])
+        );
+    }
+}
+
+fn print_diff(catalogue_diff: &diff::CatalogueDiff) {
+    let name = |identity: &diff::FilmIdentity| match &identity.source {
+        Some(source) => trf("{} ({}) at {}", &[&identity.title, &identity.year, source]),
+        None => format!("{} ({})", identity.title, identity.year),
+    };
+    for identity in &catalogue_diff.removed {
+        println!("{}", format!("- {}", name(identity)).red().bold());
+    }
+    for identity in &catalogue_diff.added {
+        println!("{}", format!("+ {}", name(identity)).green().bold());
+    }
+    for film_diff in &catalogue_diff.changed {
+        println!(
+            "{}",
+            format!("~ {}", name(&film_diff.identity)).yellow().bold()
+        );
+        if let Some(old_source) = &film_diff.old_source {
+            println!("\t{} {}", tr("was at").italic(), old_source);
+        }
+        for change in &film_diff.changes {
+            match change {
+                diff::FieldChange::Value { field, old, new } => {
+                    println!("\t{}", format!("{}:", field.key()).bold());
+                    println!("\t\t{}", format!("- {}", old).red());
+                    println!("\t\t{}", format!("+ {}", new).green());
+                }
+                diff::FieldChange::List {
+                    field,
+                    added,
+                    removed,
+                } => {
+                    println!("\t{}", format!("{}:", field.key()).bold());
+                    for item in removed {
+                        println!("\t\t{}", format!("- {}", item).red());
+                    }
+                    for item in added {
+                        println!("\t\t{}", format!("+ {}", item).green());
+                    }
+                }
+            }
+        }
+    }
+    if catalogue_diff.is_empty() {
+        println!("{}", tr("The catalogues hold the same films.").italic());
+    } else {
+        println!(
+            "{}",
+            trf(
+                "{} added, {} removed, {} changed.",
+                &[
+                    &catalogue_diff.added.len(),
+                    &catalogue_diff.removed.len(),
+                    &catalogue_diff.changed.len()
+                ]
+            )
             .green()
             .bold()
-            .italic()
+        );
+    }
+}
+
+/// Names a record by its source file and line, or by its position when the source is unknown.
+fn record_label(films: &[Film], record: usize) -> String {
+    match &films[record].source {
+        Some(source) => source.to_string(),
+        None => format!("#{}", record + 1),
+    }
+}
+
+fn print_lint_issues(issues: &[lint::LintIssue], films: &[Film]) {
+    for issue in issues {
+        let severity = format!("{}[{}]:", issue.severity, issue.rule);
+        let severity = match issue.severity {
+            lint::Severity::Error => severity.red().bold(),
+            _ => severity.yellow().bold(),
+        };
+        println!(
+            "{}: {} {} {}",
+            record_label(films, issue.film),
+            severity,
+            issue.message,
+            format!("({})", films[issue.film].title).italic()
+        );
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == lint::Severity::Error)
+        .count();
+    println!(
+        "{}",
+        trf(
+            "Checked {} films: {} errors, {} warnings.",
+            &[&films.len(), &errors, &(issues.len() - errors)]
+        )
+        .green()
+        .bold()
+    );
+}
+
+fn print_duplicates(groups: &[dedupe::DuplicateGroup], films: &[Film]) {
+    for group in groups {
+        let first = &films[group.records[0]];
+        println!(
+            "{} {} {}",
+            first.title.yellow().bold(),
+            format!("({})", first.year).italic(),
+            trf("{} records:", &[&group.records.len()]).bold()
+        );
+        for &record in &group.records {
+            let film = &films[record];
+            println!(
+                "\t{} {}",
+                record_label(films, record).bold(),
+                trf(
+                    "{}, {} genres, {} stars, description of {} characters",
+                    &[
+                        &film.title,
+                        &film.genre.len(),
+                        &film.stars.len(),
+                        &film.description.chars().count()
+                    ]
+                )
+            );
+        }
+    }
+    if groups.is_empty() {
+        println!("{}", tr("No duplicates found.").italic());
+    } else {
+        println!(
+            "{}",
+            trf("Found {} groups of duplicates.", &[&groups.len()])
+                .green()
+                .bold()
+        );
+    }
+}
+
+fn print_merged(merged: &[dedupe::MergedFilm], films: &[Film]) {
+    for merged_film in merged {
+        let records: Vec<String> = merged_film
+            .records
+            .iter()
+            .map(|&record| record_label(films, record))
+            .collect();
+        println!(
+            "{} {} {} {}",
+            merged_film.film.title.yellow().bold(),
+            format!("({})", merged_film.film.year).italic(),
+            tr("merged from").bold(),
+            records.join(", ")
+        );
+        for source in &merged_film.provenance {
+            let records: Vec<String> = source
+                .records
+                .iter()
+                .map(|&record| record_label(films, record))
+                .collect();
+            println!(
+                "\t{} {}",
+                format!("{:<12}", format!("{}:", source.field.key())).bold(),
+                records.join(", ").italic()
+            );
+        }
+    }
+    println!(
+        "{}",
+        trf(
+            "Merged {} groups of duplicates, {} of {} films left.",
+            &[
+                &merged.len(),
+                &(films.len() - merged.iter().map(|m| m.records.len() - 1).sum::<usize>()),
+                &films.len()
+            ]
+        )
+        .green()
+        .bold()
+    );
+}
+
+fn print_recommendations(recommendations: &[recommend::Recommendation]) {
+    for recommendation in recommendations {
+        let explanation = &recommendation.explanation;
+        println!(
+            "{} {} {}",
+            recommendation.film.title.yellow().bold(),
+            format!("({})", recommendation.film.year).italic(),
+            trf("score {}", &[&format!("{:.3}", recommendation.score)])
+                .green()
+                .bold()
+        );
+        let rows = [
+            (
+                tr("genre:"),
+                explanation.genre.contribution,
+                explanation.shared_genres.join(", "),
+            ),
+            (
+                tr("crew:"),
+                explanation.crew.contribution,
+                explanation.shared_crew.join(", "),
+            ),
+            (
+                tr("cast:"),
+                explanation.cast.contribution,
+                explanation.shared_cast.join(", "),
+            ),
+            (
+                tr("year:"),
+                explanation.year.contribution,
+                trf("difference of {} years", &[&explanation.year_difference]),
+            ),
+            (
+                tr("description:"),
+                explanation.description.contribution,
+                explanation.shared_terms.join(", "),
+            ),
+        ];
+        // Labels are padded to the longest one in characters, whatever the language.
+        let width = rows
+            .iter()
+            .map(|(label, _, _)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (label, contribution, details) in rows {
+            println!(
+                "\t{} +{:.3} {}",
+                format!("{:<width$}", label).bold(),
+                contribution,
+                details.italic()
+            );
+        }
+    }
+    if recommendations.is_empty() {
+        println!("{}", tr("No similar films found.").italic());
+    }
+}
+
+fn print_collaborators(collaborators: &[graph::Collaborator], top: usize) {
+    for collaborator in collaborators.iter().take(top) {
+        println!(
+            "{} {} {}",
+            collaborator.name.yellow().bold(),
+            trf("({} films):", &[&collaborator.films.len()]).bold(),
+            collaborator.films.join(", ").italic()
+        );
+    }
+    if collaborators.is_empty() {
+        println!("{}", tr("No collaborators found.").italic());
+    }
+}
+
+fn print_path(path: Option<&[&graph::Node]>) {
+    let Some(path) = path else {
+        println!("{}", tr("These people are not connected.").italic());
+        return;
+    };
+    let steps: Vec<String> = path
+        .iter()
+        .map(|node| match node.kind {
+            graph::NodeKind::Person => node.name.yellow().bold().to_string(),
+            graph::NodeKind::Film => node.name.italic().to_string(),
+        })
+        .collect();
+    println!("{}", steps.join(" -> "));
+    println!(
+        "{} {}",
+        tr("Degrees of separation:").green().bold(),
+        path.len() / 2
+    );
+}
+
+fn show_help() {
+    println!(
+        "{}",
+        format!(
+            "\n{}",
+            tr("Film Parser - A command-line tool for parsing film information from file.")
+        )
+        .green()
+        .bold()
+        .italic()
+    );
+    println!("{}", tr("Commands:").green().bold());
+    println!(
+        "{}  - {}",
+        "\tparse <filename>...".italic(),
+        tr("Parse the files, directories or glob patterns into one catalogue; duplicates are merged with --policy.")
+    );
+    println!(
+        "{} - {}",
+        "\tfilter <filename> <query>".italic(),
+        tr("Show films matching the query.")
+    );
+    println!(
+        "{}   - {}",
+        "\tstats <filename>".italic(),
+        tr("Show catalogue statistics, --json for JSON output, --top N for top lists.")
+    );
+    println!(
+        "{} - {}",
+        "\tchart <filename> [chart]".italic(),
+        tr("Draw charts: years, decades, genres, directors, stars, descriptions or all.")
+    );
+    println!(
+        "{} - {}",
+        "\tgraph <filename> collaborators <person>".italic(),
+        tr("Show who worked with the person most often, --role star|writer|director to filter.")
+    );
+    println!(
+        "{} - {}",
+        "\tgraph <filename> path <person> <person>".italic(),
+        tr("Show the shortest chain of shared films between two people.")
+    );
+    println!(
+        "{} - {}",
+        "\tgraph <filename> export dot|graphml".italic(),
+        tr("Export the graph, --nodes director,writer,star,film and --min-shared N to filter, --output <file>.")
     );
-    println!("{}", "Commands:".green().bold());
     println!(
-        "{}  - Parse the specified file and display its content.",
-        "\tparse <filename>".italic()
+        "{} - {}",
+        "\tsimilar <filename> <title>".italic(),
+        tr("Show films similar to the given one, --top N to limit.")
     );
     println!(
-        "{}              - Show this help information.",
-        "\thelp".italic()
+        "{} - {}",
+        "\tfind <filename> <words>".italic(),
+        tr("Full-text search: word* for prefixes, \"quoted words\" for phrases, --top N to limit.")
     );
     println!(
-        "{}           - Show credits information.",
-        "\tcredits".italic()
+        "{} - {}",
+        "\tlookup <filename> <text>".italic(),
+        tr("Find titles and names similar to the given text, --top N to limit.")
     );
-    println!("{}              - Run tests.", "\ttest".italic());
-    println!("{}", "\nExample usage:".green().bold());
+    println!(
+        "{} - {}",
+        "\tdedupe <filename>".italic(),
+        tr("Report likely duplicates, --merge to merge them, --policy union,longest|first, --output <file>.")
+    );
+    println!(
+        "{} - {}",
+        "\tdiff <old> <new>".italic(),
+        tr("Compare two catalogues film by film, --json for JSON output.")
+    );
+    println!(
+        "{} - {}",
+        "\tfmt <filename>...".italic(),
+        tr("Rewrite the files in canonical form, --sort-by title,year to sort, --check to only report changes, --key-lang uk to write Ukrainian keys.")
+    );
+    println!(
+        "{} - {}",
+        "\tlint <filename>...".italic(),
+        tr("Check the films against the lint rules, --config <file> to set rule severities.")
+    );
+    println!(
+        "{} - {}",
+        "\tgenerate <count> <file>".italic(),
+        tr("Write a synthetic catalogue for benchmarks, --cast N stars per film, --seed N for another catalogue.")
+    );
+    println!("{}", tr("Options for parse and filter:").green().bold());
+    println!(
+        "{}       - {}",
+        "\t--lenient".italic(),
+        tr("Repair malformed records instead of rejecting them, e.g. drop empty list items.")
+    );
+    println!(
+        "{}  - {}",
+        "\t--check-genres".italic(),
+        tr("Check every genre against the list of known genres.")
+    );
+    println!(
+        "{}  - {}",
+        "\t--keys builtin".italic(),
+        tr("Accept the keys of the built-in languages, or of a key table file, in any case.")
+    );
+    println!(
+        "{} - {}",
+        "\t--encoding windows-1251".italic(),
+        tr("Read files without a byte order mark in a legacy encoding, e.g. windows-1251 or latin1.")
+    );
+    println!(
+        "{}          - {}",
+        "\t--jobs 4".italic(),
+        tr("Parse on this many threads, one per CPU by default; needs the parallel feature.")
+    );
+    println!(
+        "{}  - {}",
+        "\t-o out.jsonl.zst".italic(),
+        tr("Write the parsed catalogue to one file, as JSON lines for .jsonl; .gz and .zst are compressed.")
+    );
+    println!(
+        "{}  - {}",
+        "\t--sort-by year,-title".italic(),
+        tr("Sort by fields, a leading '-' sorts in descending order.")
+    );
+    println!(
+        "{} - {}",
+        "\t--group-by decade".italic(),
+        tr("Group by director, writer, genre, star, year or decade (filter and search only).")
+    );
+    println!(
+        "{} - {}",
+        "\t--fields title,year".italic(),
+        tr("Show only the listed fields, in that order.")
+    );
+    println!(
+        "{}       - {}",
+        "\t--lang uk".italic(),
+        tr("Show messages in Ukrainian or English; the default is taken from LANG.")
+    );
+    println!(
+        "{}              - {}",
+        "\thelp".italic(),
+        tr("Show this help information.")
+    );
+    println!(
+        "{}           - {}",
+        "\tcredits".italic(),
+        tr("Show credits information.")
+    );
+    println!("{}              - {}", "\ttest".italic(), tr("Run tests."));
+    println!("{}", format!("\n{}", tr("Example usage:")).green().bold());
     println!("{}", "\tcargo run -- parse data/film_info.txt".italic());
+    println!(
+        "{}",
+        "\tcargo run -- parse catalogues/ 'more/*.txt' --policy first".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- filter data/film_info.txt 'year >= 2015 and genre = Horror and star ~ \"Pugh\"'"
+            .italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- filter data/film_info.txt 'genre = Drama' --sort-by -year --fields title,year"
+            .italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- stats data/film_info.txt --top 5".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- chart data/film_info.txt stars --top 5 --width 60".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- graph data/film_info.txt collaborators 'Ari Aster' --role star".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- graph data/film_info.txt path 'Florence Pugh' 'Toni Collette'".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- graph data/film_info.txt export dot --nodes director,star --min-shared 2"
+            .italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- similar data/film_info.txt Midsommar --top 5".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- find data/film_info.txt 'haunt* \"small town\"'".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- lookup data/film_info.txt 'florence pug'".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- dedupe data/film_info.txt --merge --policy union,longest --output merged.txt"
+            .italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- diff old_films.txt data/film_info.txt".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- fmt --check data/film_info.txt".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- lint data/film_info.txt --config filmlint.conf".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- fmt --keys builtin --key-lang uk data/film_info.txt".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- --lang uk stats data/film_info.txt".italic()
+    );
+    println!(
+        "{}",
+        "\tcargo run -- parse export_cp1251.txt --encoding windows-1251".italic()
+    );
     println!("{}", "\tcargo run -- help".italic());
     println!("{}", "\tcargo run -- credits".italic());
     println!("{}", "\tcargo run -- test".italic());
@@ -91,9 +1555,11 @@ fn show_help() {
 
 fn show_credits() {
     println!("{}", "Film Parser v1.0".italic());
-    println!("Developed by {}", "Rudas Vladyslava".bold());
+    println!("{} {}", tr("Developed by"), "Rudas Vladyslava".bold());
     println!(
         "{}",
-        "Thanks for using the Film Parser CLI!".italic().yellow()
+        tr("Thanks for using the Film Parser CLI!")
+            .italic()
+            .yellow()
     );
 }
@@ -0,0 +1,135 @@
+//! Memory-mapped catalogue files.
+//!
+//! `MappedText::open` maps a file into memory instead of reading it, and a `LineIndex` records
+//! where every line starts, so records are parsed straight from the mapped bytes, e.g. as borrowed
+//! `FilmRef`s, without a `String` per line. Files that have to be decompressed or transcoded, see
+//! `compression` and `encoding`, are read into memory instead, so every file can be opened this
+//! way.
+
+use crate::compression::Compression;
+use crate::encoding;
+use crate::{FilmParserError, SourceLocation};
+use encoding_rs::{Encoding, UTF_8};
+use memmap2::Mmap;
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+use std::str;
+
+/// The decoded text of a file, mapped into memory when it is plain UTF-8.
+pub struct MappedText {
+    inner: Inner,
+}
+
+enum Inner {
+    /// A UTF-8 file and the length of its byte order mark.
+    Mapped(Mmap, usize),
+    /// A file that was decompressed or transcoded.
+    Decoded(String),
+}
+
+impl MappedText {
+    /// Opens a file, mapping it if it is UTF-8 text. Files without a byte order mark are decoded
+    /// with `encoding` like by `encoding::read_file`.
+    pub fn open(path: &Path, encoding: Option<&'static Encoding>) -> Result<Self, FilmParserError> {
+        if !path.exists() {
+            return Err(FilmParserError::NoFileFound(path.display().to_string()));
+        }
+        let file = File::open(path)
+            .map_err(|_| FilmParserError::FileOpeningError(path.display().to_string()))?;
+        // SAFETY: the map is only read, and catalogue files are not expected to change while
+        // they are parsed; a file truncated meanwhile may fail the process, like any mapped file.
+        let map = unsafe { Mmap::map(&file) }
+            .map_err(|e| FilmParserError::FileReadingError(format!("{}: {}", path.display(), e)))?;
+
+        let bom_length = match Encoding::for_bom(&map) {
+            Some((bom_encoding, bom_length)) if bom_encoding == UTF_8 => Some(bom_length),
+            Some(_) => None,
+            None if encoding.is_none_or(|encoding| encoding == UTF_8) => Some(0),
+            None => None,
+        };
+        let is_mappable = Compression::detect(&map) == Compression::None
+            && bom_length.is_some_and(|start| str::from_utf8(&map[start..]).is_ok());
+        let inner = match bom_length {
+            Some(start) if is_mappable => Inner::Mapped(map, start),
+            _ => Inner::Decoded(encoding::read_file(path, encoding)?),
+        };
+        Ok(MappedText { inner })
+    }
+
+    /// Returns `true` if the file is mapped rather than read into memory.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.inner, Inner::Mapped(..))
+    }
+
+    /// The text of the file, without a byte order mark.
+    pub fn as_str(&self) -> &str {
+        match &self.inner {
+            // SAFETY: the bytes were checked to be UTF-8 when the file was mapped.
+            Inner::Mapped(map, start) => unsafe { str::from_utf8_unchecked(&map[*start..]) },
+            Inner::Decoded(text) => text,
+        }
+    }
+}
+
+/// Byte offsets of the lines of a text. Both `\n` and `\r\n` line breaks are accepted, and a
+/// line break at the end of the text does not start another line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    starts: Vec<usize>,
+    length: usize,
+}
+
+impl LineIndex {
+    /// Indexes the lines of the text.
+    pub fn new(text: &str) -> Self {
+        let mut starts = Vec::new();
+        if !text.is_empty() {
+            starts.push(0);
+        }
+        starts.extend(
+            text.bytes()
+                .enumerate()
+                .filter(|(offset, byte)| *byte == b'\n' && offset + 1 < text.len())
+                .map(|(offset, _)| offset + 1),
+        );
+        LineIndex {
+            starts,
+            length: text.len(),
+        }
+    }
+
+    /// Returns the number of lines.
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Returns `true` if the text has no lines.
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Returns the byte offsets of a line, counted from 0, without the line break.
+    pub fn span(&self, text: &str, line: usize) -> Range<usize> {
+        let start = self.starts[line];
+        let end = self.starts.get(line + 1).copied().unwrap_or(self.length);
+        let content = text[start..end]
+            .trim_end_matches('\n')
+            .trim_end_matches('\r');
+        start..start + content.len()
+    }
+
+    /// Returns a line of the text, counted from 0, without the line break.
+    pub fn line<'a>(&self, text: &'a str, line: usize) -> &'a str {
+        &text[self.span(text, line)]
+    }
+
+    /// Returns the location of a line, counted from 0, in the file at `path`.
+    pub fn location(&self, text: &str, path: &Path, line: usize) -> SourceLocation {
+        SourceLocation {
+            path: path.to_path_buf(),
+            line: line + 1,
+            byte_span: self.span(text, line),
+        }
+    }
+}
@@ -0,0 +1,612 @@
+//! Translations of the messages shown by the command-line tool.
+//!
+//! Messages are looked up by their English text, so a message without a translation is shown in
+//! English. Every `{}` in a message is replaced by the next argument of `Language::format`.
+//! `Language::error` renders a `FilmParserError` and `Language::warning` a `ParseWarning` in the
+//! language; in English they are the same text as their `Display`. The messages of query errors
+//! are formatted in English by the library and translated back by matching them against the
+//! catalogue.
+
+use crate::{FilmParserError, ParseWarning, WarningKind};
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+/// Environment variables read by `Language::from_env`, in order of precedence.
+pub const LOCALE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// Ukrainian translations, keyed by the English message.
+const UKRAINIAN: &[(&str, &str)] = &[
+    // Prefixes and labels.
+    ("Error", "Помилка"),
+    ("Warning", "Попередження"),
+    ("Error reading files ", "Помилка читання файлів "),
+    ("Error reading file ", "Помилка читання файлу "),
+    ("Tests failed:", "Тести не пройшли:"),
+    ("Tests passed successfully:", "Тести успішно пройшли:"),
+    ("title", "назва"),
+    ("person", "людина"),
+    ("Would reformat", "Буде переформатовано"),
+    ("Formatted", "Відформатовано"),
+    ("was at", "було в"),
+    ("merged from", "об'єднано з"),
+    ("genre:", "жанр:"),
+    ("crew:", "автори:"),
+    ("cast:", "актори:"),
+    ("year:", "рік:"),
+    ("description:", "опис:"),
+    ("Developed by", "Розробила"),
+    // Names of list fields, as in "the genre list".
+    ("genre", "жанрів"),
+    ("stars", "акторів"),
+    // Errors of the command line.
+    (
+        "No command provided. Use 'cargo run -- help' for usage information.",
+        "Команду не вказано. Скористайтеся 'cargo run -- help', щоб дізнатися, як працювати з програмою.",
+    ),
+    (
+        "Invalid command. Use 'cargo run -- help' for usage information.",
+        "Невідома команда. Скористайтеся 'cargo run -- help', щоб дізнатися, як працювати з програмою.",
+    ),
+    (
+        "No file path provided. You must specify path to the file with information.",
+        "Шлях до файлу не вказано. Потрібно вказати шлях до файлу з інформацією.",
+    ),
+    (
+        "You must specify path to the file and a query, e.g. 'year >= 2015 and genre = Horror'.",
+        "Потрібно вказати шлях до файлу та запит, наприклад 'year >= 2015 and genre = Horror'.",
+    ),
+    (
+        "Unknown chart '{}'. Expected years, decades, genres, directors, stars, descriptions or all.",
+        "Невідома діаграма '{}'. Очікується years, decades, genres, directors, stars, descriptions або all.",
+    ),
+    (
+        "Expected 'graph <filename> collaborators <person>', 'graph <filename> path <person> <person>' or 'graph <filename> export dot|graphml'.",
+        "Очікується 'graph <файл> collaborators <людина>', 'graph <файл> path <людина> <людина>' або 'graph <файл> export dot|graphml'.",
+    ),
+    (
+        "You must specify path to the file and a film title.",
+        "Потрібно вказати шлях до файлу та назву фільму.",
+    ),
+    (
+        "You must specify path to the file and the words to search for.",
+        "Потрібно вказати шлях до файлу та слова для пошуку.",
+    ),
+    (
+        "You must specify path to the file and a title or name.",
+        "Потрібно вказати шлях до файлу та назву або ім'я.",
+    ),
+    (
+        "You must specify the paths to the old and the new file.",
+        "Потрібно вказати шляхи до старого та нового файлу.",
+    ),
+    (
+        "You must specify the files to format.",
+        "Потрібно вказати файли для форматування.",
+    ),
+    (
+        "You must specify the files to check.",
+        "Потрібно вказати файли для перевірки.",
+    ),
+    ("{} must be a positive number.", "{} має бути додатним числом."),
+    ("Did you mean '{}'?", "Можливо, ви мали на увазі '{}'?"),
+    // Results.
+    (
+        "Merged {} duplicated films, {} films left.",
+        "Об'єднано дублікатів: {}, залишилося фільмів: {}.",
+    ),
+    ("Found {} of {} films.", "Знайдено {} з {} фільмів."),
+    ("Indexed {} films.", "Проіндексовано фільмів: {}."),
+    ("No films found.", "Фільмів не знайдено."),
+    (
+        "No similar titles or names found.",
+        "Схожих назв або імен не знайдено.",
+    ),
+    ("Wrote {} films to {}.", "Записано фільмів: {} у {}."),
+    ("Films:", "Фільмів:"),
+    ("Average cast size:", "Середній розмір акторського складу:"),
+    (
+        "Directed and written by the same person:",
+        "Режисер і сценарист — одна людина:",
+    ),
+    ("Description length:", "Довжина опису:"),
+    (
+        "min {}, max {}, mean {}, median {}",
+        "мінімум {}, максимум {}, середнє {}, медіана {}",
+    ),
+    ("Films per year", "Фільми за роками"),
+    ("Films per decade", "Фільми за десятиліттями"),
+    ("Genres", "Жанри"),
+    ("Decades", "Десятиліття"),
+    ("Years", "Роки"),
+    ("Top directors", "Найчастіші режисери"),
+    ("Top writers", "Найчастіші сценаристи"),
+    ("Top stars", "Найчастіші актори"),
+    ("Description lengths", "Довжини описів"),
+    ("{} ({}) at {}", "{} ({}) у {}"),
+    (
+        "The catalogues hold the same films.",
+        "Каталоги містять однакові фільми.",
+    ),
+    (
+        "{} added, {} removed, {} changed.",
+        "Додано: {}, видалено: {}, змінено: {}.",
+    ),
+    (
+        "Checked {} films: {} errors, {} warnings.",
+        "Перевірено фільмів: {}, помилок: {}, попереджень: {}.",
+    ),
+    ("{} records:", "записів: {}:"),
+    (
+        "{}, {} genres, {} stars, description of {} characters",
+        "{}, жанрів: {}, акторів: {}, опис із {} символів",
+    ),
+    ("No duplicates found.", "Дублікатів не знайдено."),
+    ("Found {} groups of duplicates.", "Знайдено груп дублікатів: {}."),
+    (
+        "Merged {} groups of duplicates, {} of {} films left.",
+        "Об'єднано груп дублікатів: {}, залишилося {} з {} фільмів.",
+    ),
+    ("score {}", "оцінка {}"),
+    ("difference of {} years", "різниця {} років"),
+    ("No similar films found.", "Схожих фільмів не знайдено."),
+    ("({} films):", "(фільмів: {}):"),
+    ("No collaborators found.", "Співпрацівників не знайдено."),
+    (
+        "These people are not connected.",
+        "Ці люди не пов'язані між собою.",
+    ),
+    ("Degrees of separation:", "Ступенів віддалення:"),
+    // Help and credits.
+    (
+        "Film Parser - A command-line tool for parsing film information from file.",
+        "Film Parser - інструмент командного рядка для розбору інформації про фільми з файлу.",
+    ),
+    ("Commands:", "Команди:"),
+    (
+        "Parse the files, directories or glob patterns into one catalogue; duplicates are merged with --policy.",
+        "Розібрати файли, каталоги або glob-шаблони в один каталог; дублікати об'єднуються за --policy.",
+    ),
+    (
+        "Show films matching the query.",
+        "Показати фільми, що відповідають запиту.",
+    ),
+    (
+        "Show catalogue statistics, --json for JSON output, --top N for top lists.",
+        "Показати статистику каталогу, --json для виводу в JSON, --top N для найчастіших.",
+    ),
+    (
+        "Draw charts: years, decades, genres, directors, stars, descriptions or all.",
+        "Намалювати діаграми: years, decades, genres, directors, stars, descriptions або all.",
+    ),
+    (
+        "Show who worked with the person most often, --role star|writer|director to filter.",
+        "Показати, хто найчастіше працював із людиною, --role star|writer|director для фільтра.",
+    ),
+    (
+        "Show the shortest chain of shared films between two people.",
+        "Показати найкоротший ланцюжок спільних фільмів між двома людьми.",
+    ),
+    (
+        "Export the graph, --nodes director,writer,star,film and --min-shared N to filter, --output <file>.",
+        "Експортувати граф, --nodes director,writer,star,film і --min-shared N для фільтра, --output <файл>.",
+    ),
+    (
+        "Show films similar to the given one, --top N to limit.",
+        "Показати фільми, схожі на вказаний, --top N для обмеження.",
+    ),
+    (
+        "Full-text search: word* for prefixes, \"quoted words\" for phrases, --top N to limit.",
+        "Повнотекстовий пошук: слово* для префіксів, \"слова в лапках\" для фраз, --top N для обмеження.",
+    ),
+    (
+        "Find titles and names similar to the given text, --top N to limit.",
+        "Знайти назви та імена, схожі на вказаний текст, --top N для обмеження.",
+    ),
+    (
+        "Report likely duplicates, --merge to merge them, --policy union,longest|first, --output <file>.",
+        "Показати ймовірні дублікати, --merge для об'єднання, --policy union,longest|first, --output <файл>.",
+    ),
+    (
+        "Compare two catalogues film by film, --json for JSON output.",
+        "Порівняти два каталоги фільм за фільмом, --json для виводу в JSON.",
+    ),
+    (
+        "Rewrite the files in canonical form, --sort-by title,year to sort, --check to only report changes, --key-lang uk to write Ukrainian keys.",
+        "Переписати файли в канонічній формі, --sort-by title,year для сортування, --check лише для звіту про зміни, --key-lang uk для українських ключів.",
+    ),
+    (
+        "Check the films against the lint rules, --config <file> to set rule severities.",
+        "Перевірити фільми за правилами лінтера, --config <файл> для рівнів правил.",
+    ),
+    ("Options for parse and filter:", "Параметри для parse і filter:"),
+    (
+        "Repair malformed records instead of rejecting them, e.g. drop empty list items.",
+        "Виправляти некоректні записи замість відхилення, наприклад відкидати порожні елементи списків.",
+    ),
+    (
+        "Check every genre against the list of known genres.",
+        "Перевіряти кожен жанр за списком відомих жанрів.",
+    ),
+    (
+        "Accept the keys of the built-in languages, or of a key table file, in any case.",
+        "Приймати ключі вбудованих мов або з файлу таблиці ключів у будь-якому регістрі.",
+    ),
+    (
+        "Read files without a byte order mark in a legacy encoding, e.g. windows-1251 or latin1.",
+        "Читати файли без позначки порядку байтів у застарілому кодуванні, наприклад windows-1251 або latin1.",
+    ),
+    (
+        "Parse on this many threads, one per CPU by default; needs the parallel feature.",
+        "Розбирати у стількох потоках, типово по одному на процесор; потрібна функція parallel.",
+    ),
+    (
+        "Built without the parallel feature, parsing on one thread.",
+        "Зібрано без функції parallel, розбір в одному потоці.",
+    ),
+    (
+        "Write a synthetic catalogue for benchmarks, --cast N stars per film, --seed N for another catalogue.",
+        "Записати синтетичний каталог для вимірювань, --cast N акторів на фільм, --seed N для іншого каталогу.",
+    ),
+    (
+        "You must specify the number of films and the output file.",
+        "Потрібно вказати кількість фільмів і вихідний файл.",
+    ),
+    (
+        "Write the parsed catalogue to one file, as JSON lines for .jsonl; .gz and .zst are compressed.",
+        "Записати розібраний каталог в один файл, як рядки JSON для .jsonl; .gz і .zst стискаються.",
+    ),
+    (
+        "Sort by fields, a leading '-' sorts in descending order.",
+        "Сортувати за полями, '-' на початку сортує за спаданням.",
+    ),
+    (
+        "Group by director, writer, genre, star, year or decade (filter and search only).",
+        "Групувати за режисером, сценаристом, жанром, актором, роком або десятиліттям (лише filter і search).",
+    ),
+    (
+        "--group-by can only be used with filter and search.",
+        "--group-by можна використовувати лише з filter і search.",
+    ),
+    (
+        "--fields cannot be used with --output, which writes whole records.",
+        "--fields не можна використовувати з --output, який записує записи повністю.",
+    ),
+    (
+        "Show only the listed fields, in that order.",
+        "Показувати лише вказані поля в такому порядку.",
+    ),
+    (
+        "Show messages in Ukrainian or English; the default is taken from LANG.",
+        "Показувати повідомлення українською або англійською; типово мова береться з LANG.",
+    ),
+    ("Show this help information.", "Показати цю довідку."),
+    ("Show credits information.", "Показати інформацію про авторів."),
+    ("Run tests.", "Запустити тести."),
+    ("Example usage:", "Приклади використання:"),
+    (
+        "Thanks for using the Film Parser CLI!",
+        "Дякуємо, що користуєтеся Film Parser CLI!",
+    ),
+    // Errors of the library.
+    (
+        "Specified file was not found: {}",
+        "Вказаний файл не знайдено: {}",
+    ),
+    ("Failed to read the file: {}", "Не вдалося прочитати файл: {}"),
+    ("Failed to open the file {}", "Не вдалося відкрити файл {}"),
+    ("Failed to create the file {}", "Не вдалося створити файл {}"),
+    ("Failed to write to the file {}", "Не вдалося записати у файл {}"),
+    (
+        "Failed to parse the file content: {}",
+        "Не вдалося розібрати вміст файлу: {}",
+    ),
+    (
+        "Failed to parse the rule {} content: {}",
+        "Не вдалося розібрати вміст правила {}: {}",
+    ),
+    (
+        "Item {} of the {} list is empty",
+        "Елемент {} списку {} порожній",
+    ),
+    (
+        "Missing required film fields",
+        "Бракує обов'язкових полів фільму",
+    ),
+    ("Unknown rule {}", "Невідоме правило {}"),
+    ("Unknown film field {}", "Невідоме поле фільму {}"),
+    ("Invalid option {}", "Некоректний параметр {}"),
+    ("No film titled {}", "Немає фільму з назвою {}"),
+    ("No films found for {}", "Не знайдено фільмів для {}"),
+    (
+        "Failed to serialize the output: {}",
+        "Не вдалося серіалізувати результат: {}",
+    ),
+    (
+        "Rejected in strict mode: {}",
+        "Відхилено в строгому режимі: {}",
+    ),
+    ("Invalid configuration {}", "Некоректна конфігурація {}"),
+    ("Invalid query:\n{}", "Некоректний запит:\n{}"),
+    ("Incomplete comparison", "Неповне порівняння"),
+    ("Empty value", "Порожнє значення"),
+    (
+        "unknown field `{}`, expected one of: title, year, director, writer, genre, star, description",
+        "невідоме поле `{}`, очікується одне з: title, year, director, writer, genre, star, description",
+    ),
+    (
+        "operator `~` cannot be used with `year`",
+        "оператор `~` не можна використовувати з `year`",
+    ),
+    (
+        "`year` must be compared with a number, found `{}`",
+        "`year` треба порівнювати з числом, а знайдено `{}`",
+    ),
+    (
+        "year `{}` is too large, the largest is {}",
+        "рік `{}` завеликий, найбільший — {}",
+    ),
+    (
+        "operator `{}` can only be used with `year`",
+        "оператор `{}` можна використовувати лише з `year`",
+    ),
+    (
+        "Failed to parse the file content: {} - {}",
+        "Не вдалося розібрати вміст файлу: {} - {}",
+    ),
+    // Warnings of lenient parsing, also the reasons of strict mode errors.
+    ("key {} is not in title case", "ключ {} написано не з великої літери"),
+    (
+        "unexpected whitespace around ':' after {}",
+        "зайвий пропуск біля ':' після {}",
+    ),
+    (
+        "ignored text after the record: {}",
+        "проігноровано текст після запису: {}",
+    ),
+    ("field {} is missing", "бракує поля {}"),
+    (
+        "dropped empty item {} of the {} list",
+        "вилучено порожній елемент {} списку {}",
+    ),
+    ("unknown genre {}", "невідомий жанр {}"),
+    (
+        "The index was not saved: {}",
+        "Індекс не збережено: {}",
+    ),
+];
+
+/// Language of the messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    /// English, the language the messages are written in.
+    #[default]
+    English,
+    /// Ukrainian.
+    Ukrainian,
+}
+
+impl Language {
+    /// All languages.
+    pub const ALL: [Language; 2] = [Language::English, Language::Ukrainian];
+
+    /// Returns the language code, e.g. `uk`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Ukrainian => "uk",
+        }
+    }
+
+    /// Returns the language of a locale such as `uk_UA.UTF-8`, if it is supported.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    /// Returns the language of the first set locale variable in `LOCALE_VARIABLES`, or English.
+    pub fn from_env() -> Self {
+        LOCALE_VARIABLES
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// Returns the translation of an English message, or the message itself if it has none.
+    pub fn translate(self, message: &'static str) -> &'static str {
+        let catalogue = match self {
+            Language::English => return message,
+            Language::Ukrainian => UKRAINIAN,
+        };
+        catalogue
+            .iter()
+            .find(|(english, _)| *english == message)
+            .map_or(message, |(_, translated)| translated)
+    }
+
+    /// Translates a message and replaces its `{}` placeholders with the arguments in order.
+    pub fn format(self, message: &'static str, args: &[&dyn fmt::Display]) -> String {
+        let mut result = String::new();
+        let mut args = args.iter();
+        let mut parts = self.translate(message).split("{}");
+        result.push_str(parts.next().unwrap_or_default());
+        for part in parts {
+            if let Some(arg) = args.next() {
+                result.push_str(&arg.to_string());
+            }
+            result.push_str(part);
+        }
+        result
+    }
+
+    /// Returns the name of a list field, such as `genre` in "the genre list", in this language.
+    fn list_name(self, list: &str) -> &str {
+        match list {
+            "genre" => self.translate("genre"),
+            "stars" => self.translate("stars"),
+            _ => list,
+        }
+    }
+
+    /// Translates the message of a query error, which points at the query with the message on a
+    /// line starting with `=`, or is a single line.
+    fn query_message(self, message: &str) -> String {
+        if !message.contains('\n') {
+            return self.translate_formatted(message);
+        }
+        message
+            .lines()
+            .map(|line| match line.split_once("= ") {
+                Some((prefix, text)) if prefix.trim().is_empty() => {
+                    format!("{}= {}", prefix, self.translate_formatted(text))
+                }
+                _ => line.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Translates a message already formatted in English by finding the catalogue message it was
+    /// formatted from and formatting the translation with the same arguments.
+    fn translate_formatted(self, text: &str) -> String {
+        let catalogue = match self {
+            Language::English => return text.to_string(),
+            Language::Ukrainian => UKRAINIAN,
+        };
+        for (english, translated) in catalogue {
+            if let Some(args) = match_message(english, text) {
+                let mut args = args.into_iter();
+                let mut parts = translated.split("{}");
+                let mut result = parts.next().unwrap_or_default().to_string();
+                for part in parts {
+                    result.push_str(args.next().unwrap_or_default());
+                    result.push_str(part);
+                }
+                return result;
+            }
+        }
+        text.to_string()
+    }
+
+    /// Returns the message of an error in this language.
+    pub fn error(self, error: &FilmParserError) -> String {
+        match error {
+            FilmParserError::NoFileFound(path) => {
+                self.format("Specified file was not found: {}", &[path])
+            }
+            FilmParserError::FileReadingError(message) => {
+                self.format("Failed to read the file: {}", &[message])
+            }
+            FilmParserError::FileOpeningError(path) => {
+                self.format("Failed to open the file {}", &[path])
+            }
+            FilmParserError::FileCreatingError(path) => {
+                self.format("Failed to create the file {}", &[path])
+            }
+            FilmParserError::FileWritingError(path) => {
+                self.format("Failed to write to the file {}", &[path])
+            }
+            FilmParserError::ParsingError(message) => {
+                self.format("Failed to parse the file content: {}", &[message])
+            }
+            FilmParserError::RuleParsingError(rule, content) => {
+                self.format("Failed to parse the rule {} content: {}", &[rule, content])
+            }
+            FilmParserError::EmptyListItem(list, index) => self.format(
+                "Item {} of the {} list is empty",
+                &[index, &self.list_name(list)],
+            ),
+            FilmParserError::MissingFieldsError => {
+                self.translate("Missing required film fields").to_string()
+            }
+            FilmParserError::UnknownRule(rule) => self.format("Unknown rule {}", &[rule]),
+            FilmParserError::UnknownField(field) => self.format("Unknown film field {}", &[field]),
+            FilmParserError::InvalidOption(option) => self.format("Invalid option {}", &[option]),
+            FilmParserError::FilmNotFound(title) => self.format("No film titled {}", &[title]),
+            FilmParserError::PersonNotFound(name) => self.format("No films found for {}", &[name]),
+            FilmParserError::SerializationError(message) => {
+                self.format("Failed to serialize the output: {}", &[message])
+            }
+            FilmParserError::AtSource(location, error) => {
+                format!("{}: {}", location, self.error(error))
+            }
+            FilmParserError::StrictModeError(warning) => self.format(
+                "Rejected in strict mode: {}",
+                &[&self.warning_kind(warning)],
+            ),
+            FilmParserError::ConfigError(message) => {
+                self.format("Invalid configuration {}", &[message])
+            }
+            FilmParserError::QueryError(message) => {
+                self.format("Invalid query:\n{}", &[&self.query_message(message)])
+            }
+            FilmParserError::RecordError(line, error) => self.format(
+                "Failed to parse the file content: {} - {}",
+                &[line, &self.error(error)],
+            ),
+        }
+    }
+
+    /// Returns a warning with its location, if known, in this language.
+    pub fn warning(self, warning: &ParseWarning) -> String {
+        match &warning.location {
+            Some(location) => format!("{}: {}", location, self.warning_kind(&warning.kind)),
+            None => self.warning_kind(&warning.kind),
+        }
+    }
+
+    /// Returns the text of a warning kind in this language.
+    pub fn warning_kind(self, kind: &WarningKind) -> String {
+        match kind {
+            WarningKind::KeyCase(key) => self.format("key {} is not in title case", &[key]),
+            WarningKind::KeyWhitespace(field) => {
+                self.format("unexpected whitespace around ':' after {}", &[&field.key()])
+            }
+            WarningKind::TrailingText(text) => {
+                self.format("ignored text after the record: {}", &[text])
+            }
+            WarningKind::MissingField(field) => self.format("field {} is missing", &[&field.key()]),
+            WarningKind::EmptyListItem(list, index) => self.format(
+                "dropped empty item {} of the {} list",
+                &[index, &self.list_name(list)],
+            ),
+            WarningKind::UnknownGenre(genre) => self.format("unknown genre {}", &[genre]),
+        }
+    }
+}
+
+/// Returns the arguments that formatting `message` with them gives `text`, if it does.
+fn match_message<'a>(message: &str, text: &'a str) -> Option<Vec<&'a str>> {
+    let mut parts = message.split("{}");
+    let mut rest = text.strip_prefix(parts.next().unwrap_or_default())?;
+    let mut parts = parts.peekable();
+    let mut args = Vec::new();
+    while let Some(part) = parts.next() {
+        let end = if parts.peek().is_none() {
+            rest.strip_suffix(part)?.len()
+        } else {
+            rest.find(part)?
+        };
+        args.push(&rest[..end]);
+        rest = &rest[end + part.len()..];
+    }
+    rest.is_empty().then_some(args)
+}
+
+impl FromStr for Language {
+    type Err = FilmParserError;
+
+    /// Parses a language code such as `uk`, or a locale such as `uk_UA.UTF-8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_locale(s.trim())
+            .ok_or_else(|| FilmParserError::InvalidOption(format!("language {}", s)))
+    }
+}
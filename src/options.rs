@@ -0,0 +1,189 @@
+//! Options controlling how strictly film records are parsed.
+//!
+//! Strict mode accepts records exactly as `Film::to_record_line` writes them: keys in title case,
+//! `: ` after every key, `;` between fields and every field present. The description is the last
+//! field and runs to the end of the record, so it may contain `;`.
+//! Lenient mode also accepts whitespace around `:` and `;`, keys in any case, text after a record
+//! without a description, missing fields other than the title and year, and empty list items,
+//! which are dropped. Everything lenient mode tolerates is reported as a `ParseWarning`.
+//!
+//! Genres are only checked when a list of known genres is set with
+//! `ParseOptions::with_known_genres`; unknown genres are then an error in strict mode and a
+//! warning in lenient mode.
+//!
+//! With a `KeyTable` set by `ParseOptions::with_key_table`, keys of any language in the table are
+//! accepted in any case, in both modes.
+//!
+//! With the `parallel` feature, records are parsed in chunks on `ParseOptions::jobs` threads. The
+//! films, warnings and the first error come out in the order of the records either way.
+
+use crate::keys::KeyTable;
+use crate::{FilmField, SourceLocation};
+use encoding_rs::Encoding;
+use std::fmt;
+
+/// Genres accepted when `ParseOptions::with_known_genres` is used with this list.
+pub const KNOWN_GENRES: [&str; 22] = [
+    "Action",
+    "Adventure",
+    "Animation",
+    "Biography",
+    "Comedy",
+    "Crime",
+    "Documentary",
+    "Drama",
+    "Family",
+    "Fantasy",
+    "History",
+    "Horror",
+    "Music",
+    "Musical",
+    "Mystery",
+    "Romance",
+    "Science Fiction",
+    "Sport",
+    "Thriller",
+    "TV Movie",
+    "War",
+    "Western",
+];
+
+/// How records that are not quite well-formed are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Malformed records are rejected with an error.
+    #[default]
+    Strict,
+    /// Problems that can be repaired are repaired and reported as warnings.
+    Lenient,
+}
+
+/// Options passed to the parsing entry points.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// How malformed records are handled.
+    pub mode: ParseMode,
+    /// Genres a record may list, compared ignoring case; any genre is accepted when `None`.
+    pub known_genres: Option<Vec<String>>,
+    /// Localised keys accepted in any case; only the English keys in title case when `None`.
+    pub keys: Option<KeyTable>,
+    /// Encoding of files without a byte order mark; UTF-8 when `None`.
+    pub encoding: Option<&'static Encoding>,
+    /// Threads parsing records with the `parallel` feature; 0 uses one thread per CPU.
+    /// Records are parsed on the calling thread without the feature.
+    pub jobs: usize,
+}
+
+impl ParseOptions {
+    /// Options that reject malformed records.
+    pub fn strict() -> Self {
+        ParseOptions {
+            mode: ParseMode::Strict,
+            known_genres: None,
+            keys: None,
+            encoding: None,
+            jobs: 0,
+        }
+    }
+
+    /// Options that repair malformed records where possible.
+    pub fn lenient() -> Self {
+        ParseOptions {
+            mode: ParseMode::Lenient,
+            known_genres: None,
+            keys: None,
+            encoding: None,
+            jobs: 0,
+        }
+    }
+
+    /// Checks the genres of every record against the given list.
+    pub fn with_known_genres<S: AsRef<str>>(mut self, genres: &[S]) -> Self {
+        self.known_genres = Some(genres.iter().map(|g| g.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Accepts the keys of the table in any case.
+    pub fn with_key_table(mut self, keys: KeyTable) -> Self {
+        self.keys = Some(keys);
+        self
+    }
+
+    /// Reads files without a byte order mark in the encoding, e.g. `encoding_rs::WINDOWS_1251`.
+    pub fn with_encoding(mut self, encoding: &'static Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    /// Parses records on the given number of threads, see `jobs`.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Returns `true` in lenient mode.
+    pub fn is_lenient(&self) -> bool {
+        self.mode == ParseMode::Lenient
+    }
+
+    /// Returns `true` if the genre is known, or if genres are not checked.
+    pub fn is_known_genre(&self, genre: &str) -> bool {
+        self.known_genres.as_ref().is_none_or(|known| {
+            known
+                .iter()
+                .any(|known| known.trim().eq_ignore_ascii_case(genre.trim()))
+        })
+    }
+}
+
+/// Something lenient mode accepted that strict mode rejects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A key not written in title case, e.g. `title:`.
+    KeyCase(String),
+    /// Whitespace other than a single space after `:`, e.g. `Title :Raw`.
+    KeyWhitespace(FilmField),
+    /// Text after the record that is not part of any field.
+    TrailingText(String),
+    /// A field that is missing or empty.
+    MissingField(FilmField),
+    /// A dropped empty item of the named list, counted from 1.
+    EmptyListItem(String, usize),
+    /// A genre that is not in `ParseOptions::known_genres`.
+    UnknownGenre(String),
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarningKind::KeyCase(key) => write!(f, "key {} is not in title case", key),
+            WarningKind::KeyWhitespace(field) => {
+                write!(f, "unexpected whitespace around ':' after {}", field.key())
+            }
+            WarningKind::TrailingText(text) => write!(f, "ignored text after the record: {}", text),
+            WarningKind::MissingField(field) => write!(f, "field {} is missing", field.key()),
+            WarningKind::EmptyListItem(list, index) => {
+                write!(f, "dropped empty item {} of the {} list", index, list)
+            }
+            WarningKind::UnknownGenre(genre) => write!(f, "unknown genre {}", genre),
+        }
+    }
+}
+
+/// A problem tolerated in lenient mode, with the location of its record if known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// What was tolerated.
+    pub kind: WarningKind,
+    /// Where the record was read from, if known.
+    pub location: Option<SourceLocation>,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{}: {}", location, self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
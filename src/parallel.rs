@@ -0,0 +1,86 @@
+//! Parsing records on several threads.
+//!
+//! Records are independent, so with the `parallel` feature they are split into chunks of
+//! `CHUNK_SIZE` records that are parsed on a thread pool of `ParseOptions::jobs` threads. The
+//! default of one thread per CPU uses rayon's global pool. The last pool of another size is kept
+//! for the next call and replaced when the size changes. The results are put back in record
+//! order, and the error of the first broken record is returned, just like when the records are
+//! parsed one by one. Without the feature, or with one job or a single chunk, the records are
+//! parsed on the calling thread.
+
+use crate::FilmParserError;
+#[cfg(feature = "parallel")]
+use std::sync::{Arc, Mutex};
+
+/// Number of records parsed together on one thread.
+pub const CHUNK_SIZE: usize = 512;
+
+/// Maps the records with `parse` on `jobs` threads, 0 meaning one per CPU, and returns the results
+/// in record order or the error of the first record that failed.
+pub fn map_records<T, R, F>(
+    records: Vec<T>,
+    jobs: usize,
+    parse: F,
+) -> Result<Vec<R>, FilmParserError>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> Result<R, FilmParserError> + Sync,
+{
+    #[cfg(feature = "parallel")]
+    if jobs != 1 && records.len() > CHUNK_SIZE {
+        return map_chunks(records, jobs, parse);
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = jobs;
+    records.into_iter().map(parse).collect()
+}
+
+#[cfg(feature = "parallel")]
+fn map_chunks<T, R, F>(records: Vec<T>, jobs: usize, parse: F) -> Result<Vec<R>, FilmParserError>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> Result<R, FilmParserError> + Sync,
+{
+    use rayon::prelude::*;
+
+    let map = || -> Vec<Result<Vec<R>, FilmParserError>> {
+        records
+            .into_par_iter()
+            .chunks(CHUNK_SIZE)
+            .map(|chunk| chunk.into_iter().map(&parse).collect())
+            .collect()
+    };
+    let chunks = if jobs == 0 {
+        map()
+    } else {
+        thread_pool(jobs)?.install(map)
+    };
+    let mut results = Vec::new();
+    for chunk in chunks {
+        results.extend(chunk?);
+    }
+    Ok(results)
+}
+
+/// Returns a pool of `jobs` threads, reusing the last one if it has that size. Only one pool is
+/// kept; the previous one is dropped once the calls using it have finished.
+#[cfg(feature = "parallel")]
+fn thread_pool(jobs: usize) -> Result<Arc<rayon::ThreadPool>, FilmParserError> {
+    static POOL: Mutex<Option<(usize, Arc<rayon::ThreadPool>)>> = Mutex::new(None);
+
+    let mut cached = POOL.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((size, pool)) = cached.as_ref() {
+        if *size == jobs {
+            return Ok(Arc::clone(pool));
+        }
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map(Arc::new)
+        .map_err(|e| FilmParserError::InvalidOption(format!("jobs {}: {}", jobs, e)))?;
+    *cached = Some((jobs, Arc::clone(&pool)));
+    Ok(pool)
+}
@@ -0,0 +1,300 @@
+//! A small filter language for selecting films, e.g. `year >= 2015 and genre = Horror and star ~ "Pugh"`.
+//!
+//! Queries are parsed with the grammar in `query.pest` and evaluated against `Film` values.
+//! Text comparisons ignore case. For list fields (`genre`, `star`) `=` and `~` match when any
+//! item matches, while `!=` matches when no item is equal to the value.
+
+use crate::messages::Language;
+use crate::{Film, FilmField, FilmParserError, FilmRef};
+use pest::error::{Error, ErrorVariant};
+use pest::iterators::Pair;
+use pest::{Parser, Span};
+use pest_derive::Parser;
+
+#[derive(Parser)]
+#[grammar = "query.pest"]
+/// Grammar rules of the query language.
+pub struct QueryParser;
+
+/// Comparison operators supported by the query language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `=`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `~`, the field contains the value.
+    Contains,
+}
+
+impl Operator {
+    fn from_symbol(s: &str) -> Self {
+        match s {
+            "=" => Operator::Eq,
+            "!=" => Operator::Ne,
+            "<" => Operator::Lt,
+            "<=" => Operator::Le,
+            ">" => Operator::Gt,
+            ">=" => Operator::Ge,
+            _ => Operator::Contains,
+        }
+    }
+
+    fn is_ordering(self) -> bool {
+        matches!(
+            self,
+            Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge
+        )
+    }
+}
+
+/// A single `<field> <operator> <value>` condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// The compared field.
+    pub field: FilmField,
+    /// The comparison operator.
+    pub operator: Operator,
+    /// The value, lowercased for text fields.
+    pub value: String,
+}
+
+/// Parsed query expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Matches when any of the inner expressions matches.
+    Or(Vec<Expr>),
+    /// Matches when all of the inner expressions match.
+    And(Vec<Expr>),
+    /// Matches when the inner expression does not match.
+    Not(Box<Expr>),
+    /// A single condition.
+    Compare(Comparison),
+}
+
+/// A parsed query that can be matched against films.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    source: String,
+    expr: Expr,
+}
+
+impl Query {
+    /// Parses a query string.
+    ///
+    /// Syntax errors, unknown fields and operators that do not apply to a field are reported
+    /// as `FilmParserError::QueryError` with the position of the offending part of the query.
+    pub fn parse(input: &str) -> Result<Self, FilmParserError> {
+        let mut pairs = QueryParser::parse(Rule::query, input)
+            .map_err(|e| FilmParserError::QueryError(Self::rename_rules(e).to_string()))?;
+        let expr_pair = pairs
+            .next()
+            .and_then(|query| query.into_inner().next())
+            .ok_or_else(|| FilmParserError::QueryError("Empty query".to_string()))?;
+
+        Ok(Query {
+            source: input.to_string(),
+            expr: Self::parse_expr(expr_pair)?,
+        })
+    }
+
+    /// Returns the text the query was parsed from.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the parsed expression tree.
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    /// Checks whether the film satisfies the query.
+    pub fn matches(&self, film: &Film) -> bool {
+        self.matches_ref(&film.as_film_ref())
+    }
+
+    /// Checks whether the borrowed film satisfies the query without copying its text.
+    pub fn matches_ref(&self, film: &FilmRef) -> bool {
+        Self::eval(&self.expr, film)
+    }
+
+    fn parse_expr(pair: Pair<Rule>) -> Result<Expr, FilmParserError> {
+        match pair.as_rule() {
+            Rule::expr | Rule::term => {
+                let is_or = pair.as_rule() == Rule::expr;
+                let mut items = pair
+                    .into_inner()
+                    .filter(|inner| !matches!(inner.as_rule(), Rule::or_op | Rule::and_op))
+                    .map(Self::parse_expr)
+                    .collect::<Result<Vec<Expr>, FilmParserError>>()?;
+                if items.len() == 1 {
+                    Ok(items.remove(0))
+                } else if is_or {
+                    Ok(Expr::Or(items))
+                } else {
+                    Ok(Expr::And(items))
+                }
+            }
+            Rule::factor => {
+                let mut inner = pair.into_inner();
+                let first = inner
+                    .next()
+                    .ok_or_else(|| FilmParserError::QueryError("Empty factor".to_string()))?;
+                if first.as_rule() == Rule::not_op {
+                    let negated = inner
+                        .next()
+                        .ok_or_else(|| FilmParserError::QueryError("Empty factor".to_string()))?;
+                    Ok(Expr::Not(Box::new(Self::parse_expr(negated)?)))
+                } else {
+                    Self::parse_expr(first)
+                }
+            }
+            Rule::comparison => Self::parse_comparison(pair).map(Expr::Compare),
+            rule => Err(FilmParserError::UnknownRule(format!("{:?}", rule))),
+        }
+    }
+
+    fn parse_comparison(pair: Pair<Rule>) -> Result<Comparison, FilmParserError> {
+        let mut inner = pair.into_inner();
+        let (Some(field_pair), Some(operator_pair), Some(value_pair)) =
+            (inner.next(), inner.next(), inner.next())
+        else {
+            return Err(FilmParserError::QueryError(
+                "Incomplete comparison".to_string(),
+            ));
+        };
+
+        let field = field_pair.as_str().parse::<FilmField>().map_err(|_| {
+            Self::span_error(
+                field_pair.as_span(),
+                "unknown field `{}`, expected one of: title, year, director, writer, genre, star, description",
+                &[&field_pair.as_str()],
+            )
+        })?;
+        let operator = Operator::from_symbol(operator_pair.as_str());
+
+        let value_span = value_pair.as_span();
+        let value_inner = value_pair
+            .into_inner()
+            .next()
+            .ok_or_else(|| FilmParserError::QueryError("Empty value".to_string()))?;
+        let is_number = value_inner.as_rule() == Rule::number;
+        let value = match value_inner.as_rule() {
+            Rule::quoted => value_inner
+                .into_inner()
+                .next()
+                .map(|text| text.as_str().to_string())
+                .unwrap_or_default(),
+            _ => value_inner.as_str().to_string(),
+        };
+
+        if field == FilmField::Year {
+            if operator == Operator::Contains {
+                return Err(Self::span_error(
+                    operator_pair.as_span(),
+                    "operator `~` cannot be used with `year`",
+                    &[],
+                ));
+            }
+            if !is_number {
+                return Err(Self::span_error(
+                    value_span,
+                    "`year` must be compared with a number, found `{}`",
+                    &[&value],
+                ));
+            }
+            if value.parse::<u32>().is_err() {
+                return Err(Self::span_error(
+                    value_span,
+                    "year `{}` is too large, the largest is {}",
+                    &[&value, &u32::MAX],
+                ));
+            }
+        } else if operator.is_ordering() {
+            return Err(Self::span_error(
+                operator_pair.as_span(),
+                "operator `{}` can only be used with `year`",
+                &[&operator_pair.as_str()],
+            ));
+        }
+
+        Ok(Comparison {
+            field,
+            operator,
+            value: value.to_lowercase(),
+        })
+    }
+
+    fn eval(expr: &Expr, film: &FilmRef) -> bool {
+        match expr {
+            Expr::Or(items) => items.iter().any(|item| Self::eval(item, film)),
+            Expr::And(items) => items.iter().all(|item| Self::eval(item, film)),
+            Expr::Not(inner) => !Self::eval(inner, film),
+            Expr::Compare(comparison) => Self::compare(comparison, film),
+        }
+    }
+
+    fn compare(comparison: &Comparison, film: &FilmRef) -> bool {
+        let value = comparison.value.as_str();
+
+        if comparison.field == FilmField::Year {
+            let Ok(expected) = value.parse::<u32>() else {
+                return false;
+            };
+            return match comparison.operator {
+                Operator::Eq => film.year == expected,
+                Operator::Ne => film.year != expected,
+                Operator::Lt => film.year < expected,
+                Operator::Le => film.year <= expected,
+                Operator::Gt => film.year > expected,
+                Operator::Ge => film.year >= expected,
+                Operator::Contains => false,
+            };
+        }
+
+        let mut items = film.text_values(comparison.field).iter();
+        let equals = |item: &str| item.chars().flat_map(char::to_lowercase).eq(value.chars());
+        match comparison.operator {
+            Operator::Eq => items.any(|item| equals(item)),
+            Operator::Ne => items.all(|item| !equals(item)),
+            Operator::Contains => items.any(|item| item.to_lowercase().contains(value)),
+            _ => false,
+        }
+    }
+
+    /// Points at `span` with a message from the catalogue in `messages`, which
+    /// `Language::error` translates back.
+    fn span_error(
+        span: Span,
+        message: &'static str,
+        args: &[&dyn std::fmt::Display],
+    ) -> FilmParserError {
+        let message = Language::English.format(message, args);
+        let error: Error<Rule> = Error::new_from_span(ErrorVariant::CustomError { message }, span);
+        FilmParserError::QueryError(error.to_string())
+    }
+
+    fn rename_rules(error: Error<Rule>) -> Error<Rule> {
+        error.renamed_rules(|rule| {
+            match rule {
+                Rule::field => "field name",
+                Rule::operator => "operator (=, !=, <, <=, >, >=, ~)",
+                Rule::value | Rule::quoted | Rule::number | Rule::word => "value",
+                Rule::or_op => "`or`",
+                Rule::and_op => "`and`",
+                Rule::not_op => "`not`",
+                Rule::EOI => "end of query",
+                _ => "expression",
+            }
+            .to_string()
+        })
+    }
+}
@@ -0,0 +1,280 @@
+//! Content-based recommendations: films similar to a given film.
+//!
+//! The score of a candidate combines genre overlap, shared crew (director and writer), shared cast,
+//! year proximity and the TF-IDF cosine similarity of the descriptions. Crew and cast are scored
+//! apart, so a shared director is not diluted by a long cast list. Each component is a similarity from 0 to 1
+//! multiplied by its weight, and the explanation keeps every contribution.
+
+use crate::stats::ratio;
+use crate::{Film, FilmParserError};
+use std::collections::{HashMap, HashSet};
+
+/// Number of years after which two films get no year proximity score.
+pub const YEAR_WINDOW: f64 = 20.0;
+
+/// Number of shared description terms listed in an explanation.
+const SHARED_TERMS: usize = 5;
+
+/// Common English words ignored when comparing descriptions.
+const STOP_WORDS: [&str; 40] = [
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have", "her",
+    "his", "in", "into", "is", "it", "its", "of", "on", "or", "she", "that", "the", "their",
+    "them", "they", "this", "to", "was", "when", "where", "which", "who", "will", "with", "he",
+    "after",
+];
+
+/// Weights of the score components. They do not need to add up to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weights {
+    /// Weight of the genre overlap.
+    pub genre: f64,
+    /// Weight of the shared directors and writers.
+    pub crew: f64,
+    /// Weight of the shared stars.
+    pub cast: f64,
+    /// Weight of the year proximity.
+    pub year: f64,
+    /// Weight of the description similarity.
+    pub description: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            genre: 0.35,
+            crew: 0.25,
+            cast: 0.15,
+            year: 0.1,
+            description: 0.3,
+        }
+    }
+}
+
+/// One part of a recommendation score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Component {
+    /// Similarity from 0 to 1.
+    pub similarity: f64,
+    /// `similarity` multiplied by the component weight.
+    pub contribution: f64,
+}
+
+impl Component {
+    fn new(similarity: f64, weight: f64) -> Self {
+        Component {
+            similarity,
+            contribution: similarity * weight,
+        }
+    }
+}
+
+/// Why a film was recommended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    /// Genre overlap (Jaccard index of the genre sets).
+    pub genre: Component,
+    /// Shared directors and writers (Jaccard index of the crew sets).
+    pub crew: Component,
+    /// Shared stars (Jaccard index of the cast sets).
+    pub cast: Component,
+    /// Year proximity, falling linearly to 0 over `YEAR_WINDOW` years.
+    pub year: Component,
+    /// Cosine similarity of the TF-IDF vectors of the descriptions.
+    pub description: Component,
+    /// Genres of both films.
+    pub shared_genres: Vec<String>,
+    /// Directors and writers credited on both films.
+    pub shared_crew: Vec<String>,
+    /// Stars credited on both films.
+    pub shared_cast: Vec<String>,
+    /// Absolute difference between the release years.
+    pub year_difference: u32,
+    /// The description terms contributing most to the description similarity.
+    pub shared_terms: Vec<String>,
+}
+
+/// A recommended film with its score.
+#[derive(Debug, Clone)]
+pub struct Recommendation<'a> {
+    /// The recommended film.
+    pub film: &'a Film,
+    /// Sum of the component contributions.
+    pub score: f64,
+    /// The score components.
+    pub explanation: Explanation,
+}
+
+/// Ranks the films of a catalogue by similarity to a given film.
+#[derive(Debug, Clone)]
+pub struct Recommender<'a> {
+    films: &'a [Film],
+    weights: Weights,
+    descriptions: Vec<HashMap<String, f64>>,
+}
+
+impl<'a> Recommender<'a> {
+    /// Prepares the description vectors of the films with the default weights.
+    pub fn new(films: &'a [Film]) -> Self {
+        Self::with_weights(films, Weights::default())
+    }
+
+    /// Prepares the description vectors of the films with the given weights.
+    pub fn with_weights(films: &'a [Film], weights: Weights) -> Self {
+        let documents: Vec<Vec<String>> = films.iter().map(|f| tokenize(&f.description)).collect();
+
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for document in &documents {
+            let unique: HashSet<&str> = document.iter().map(String::as_str).collect();
+            for term in unique {
+                *document_frequency.entry(term).or_insert(0) += 1;
+            }
+        }
+
+        let descriptions = documents
+            .iter()
+            .map(|document| {
+                let mut vector: HashMap<String, f64> = HashMap::new();
+                for term in document {
+                    *vector.entry(term.clone()).or_insert(0.0) += 1.0;
+                }
+                for (term, weight) in vector.iter_mut() {
+                    let idf =
+                        (films.len() as f64 / document_frequency[term.as_str()] as f64).ln() + 1.0;
+                    *weight = *weight / document.len() as f64 * idf;
+                }
+                vector
+            })
+            .collect();
+
+        Recommender {
+            films,
+            weights,
+            descriptions,
+        }
+    }
+
+    /// Finds a film by title, ignoring case. The first match wins.
+    pub fn find(&self, title: &str) -> Result<usize, FilmParserError> {
+        let key = title.trim().to_lowercase();
+        self.films
+            .iter()
+            .position(|film| film.title.trim().to_lowercase() == key)
+            .ok_or_else(|| FilmParserError::FilmNotFound(title.to_string()))
+    }
+
+    /// Returns up to `limit` films most similar to the film with the given title,
+    /// best first. The film itself is not included.
+    pub fn similar(
+        &self,
+        title: &str,
+        limit: usize,
+    ) -> Result<Vec<Recommendation<'a>>, FilmParserError> {
+        let target = self.find(title)?;
+        let mut recommendations: Vec<Recommendation<'a>> = (0..self.films.len())
+            .filter(|&index| index != target)
+            .map(|index| self.score(target, index))
+            .filter(|recommendation| recommendation.score > 0.0)
+            .collect();
+        recommendations.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.film.title.cmp(&b.film.title))
+        });
+        recommendations.truncate(limit);
+        Ok(recommendations)
+    }
+
+    /// Scores the film at `candidate` against the film at `target`.
+    pub fn score(&self, target: usize, candidate: usize) -> Recommendation<'a> {
+        let a = &self.films[target];
+        let b = &self.films[candidate];
+
+        let (genre_similarity, shared_genres) = jaccard(&a.genre, &b.genre);
+        let (crew_similarity, shared_crew) = jaccard(&crew(a), &crew(b));
+        let (cast_similarity, shared_cast) = jaccard(&a.stars, &b.stars);
+
+        let year_difference = a.year.abs_diff(b.year);
+        let year_similarity = (1.0 - year_difference as f64 / YEAR_WINDOW).max(0.0);
+
+        let (description_similarity, shared_terms) =
+            cosine(&self.descriptions[target], &self.descriptions[candidate]);
+
+        let explanation = Explanation {
+            genre: Component::new(genre_similarity, self.weights.genre),
+            crew: Component::new(crew_similarity, self.weights.crew),
+            cast: Component::new(cast_similarity, self.weights.cast),
+            year: Component::new(year_similarity, self.weights.year),
+            description: Component::new(description_similarity, self.weights.description),
+            shared_genres,
+            shared_crew,
+            shared_cast,
+            year_difference,
+            shared_terms,
+        };
+        Recommendation {
+            film: b,
+            score: explanation.genre.contribution
+                + explanation.crew.contribution
+                + explanation.cast.contribution
+                + explanation.year.contribution
+                + explanation.description.contribution,
+            explanation,
+        }
+    }
+}
+
+/// Splits text into lowercase words, leaving out stop words and words shorter than three letters.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 3 && !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+fn crew(film: &Film) -> Vec<String> {
+    vec![film.director.clone(), film.writer.clone()]
+}
+
+/// Jaccard index of two lists compared ignoring case, and the items of `b` also in `a`, each once.
+fn jaccard(a: &[String], b: &[String]) -> (f64, Vec<String>) {
+    let a_set = normalized_set(a);
+    let b_set = normalized_set(b);
+    let mut shared: Vec<String> = Vec::new();
+    for item in b {
+        if a_set.contains(&item.trim().to_lowercase()) && !shared.contains(item) {
+            shared.push(item.clone());
+        }
+    }
+    let union = a_set.union(&b_set).count();
+    (ratio(a_set.intersection(&b_set).count(), union), shared)
+}
+
+fn normalized_set(values: &[String]) -> HashSet<String> {
+    values
+        .iter()
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Cosine similarity of two sparse vectors and the terms contributing most to it.
+fn cosine(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> (f64, Vec<String>) {
+    let norm = |vector: &HashMap<String, f64>| vector.values().map(|w| w * w).sum::<f64>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 {
+        return (0.0, Vec::new());
+    }
+
+    let mut products: Vec<(&String, f64)> = a
+        .iter()
+        .filter_map(|(term, weight)| b.get(term).map(|other| (term, weight * other)))
+        .collect();
+    let similarity = products.iter().fold(0.0, |sum, (_, product)| sum + product) / norms;
+    products.sort_by(|x, y| y.1.total_cmp(&x.1).then_with(|| x.0.cmp(y.0)));
+    let terms = products
+        .into_iter()
+        .take(SHARED_TERMS)
+        .map(|(term, _)| term.clone())
+        .collect();
+    (similarity, terms)
+}
@@ -0,0 +1,257 @@
+//! Loading one catalogue from several input files.
+//!
+//! Inputs can be file paths, glob patterns such as `data/*.txt`, or directories, which are walked
+//! recursively for files with the `CATALOGUE_EXTENSION` extension. Every film keeps the file and
+//! line it was read from, and duplicates across the files are merged with a `MergePolicy`.
+//!
+//! Parse errors of records read with `parse_file` or `load_files` are wrapped in
+//! `FilmParserError::AtSource`, so they name the file and line of the broken record.
+
+use crate::compression;
+use crate::dedupe::{self, Deduplicated, MergePolicy};
+use crate::mapped::{LineIndex, MappedText};
+use crate::parallel;
+use crate::{with_film_ref, Film, FilmParserError, FilmRef, ParseOptions, ParseWarning};
+use encoding_rs::Encoding;
+use serde::Serialize;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Extension of the catalogue files picked up when walking a directory, also when followed by
+/// `.gz` or `.zst`.
+pub const CATALOGUE_EXTENSION: &str = "txt";
+
+/// The file, line and bytes a film record was read from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceLocation {
+    /// Path of the file, as it was given or found.
+    pub path: PathBuf,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Byte offsets of the record, without the line break, in the decoded and decompressed UTF-8
+    /// text of the file without a byte order mark. They are offsets into the file itself only for
+    /// uncompressed UTF-8 files without a mark.
+    pub byte_span: Range<usize>,
+}
+
+/// A parsed record: the film if it was kept and the warnings, both with the record's location.
+type ParsedRecord = (Option<Film>, Vec<ParseWarning>);
+
+/// A line of a source file with its location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLine {
+    /// The line without the line break.
+    pub text: String,
+    /// Where the line is in the file.
+    pub location: SourceLocation,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)
+    }
+}
+
+/// Expands file paths, glob patterns and directories into a list of files.
+/// Files found by a pattern or in a directory are sorted; a file named twice is listed once.
+pub fn expand_inputs(inputs: &[String]) -> Result<Vec<PathBuf>, FilmParserError> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        let mut found: Vec<PathBuf> = if path.is_dir() {
+            WalkDir::new(path)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+                .map(|entry| entry.into_path())
+                .filter(|file| {
+                    compression::uncompressed_path(file)
+                        .extension()
+                        .is_some_and(|extension| extension == CATALOGUE_EXTENSION)
+                })
+                .collect()
+        } else if is_pattern(input) {
+            glob::glob(input)
+                .map_err(|e| FilmParserError::InvalidOption(format!("pattern {}: {}", input, e)))?
+                .filter_map(Result::ok)
+                .filter(|file| file.is_file())
+                .collect()
+        } else if path.exists() {
+            vec![path.to_path_buf()]
+        } else {
+            return Err(FilmParserError::NoFileFound(input.clone()));
+        };
+        if found.is_empty() {
+            return Err(FilmParserError::NoFileFound(input.clone()));
+        }
+        found.sort();
+        for file in found {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Reads the lines of a file with their line numbers and byte spans.
+/// Both `\n` and `\r\n` line breaks are accepted.
+pub fn read_source_lines(path: &Path) -> Result<Vec<SourceLine>, FilmParserError> {
+    read_source_lines_with(path, None)
+}
+
+/// Reads the lines of a file like `read_source_lines`, decoding files without a byte order mark
+/// with the given encoding. The byte spans refer to the decoded text.
+pub fn read_source_lines_with(
+    path: &Path,
+    encoding: Option<&'static Encoding>,
+) -> Result<Vec<SourceLine>, FilmParserError> {
+    let text = MappedText::open(path, encoding)?;
+    let text = text.as_str();
+    let index = LineIndex::new(text);
+    Ok((0..index.len())
+        .map(|line| SourceLine {
+            text: index.line(text, line).to_string(),
+            location: index.location(text, path, line),
+        })
+        .collect())
+}
+
+/// Parses source lines into films that remember their location. Blank lines are skipped.
+/// A parse error is reported with the location of its line, and so is every warning.
+/// Lines are parsed on `ParseOptions::jobs` threads with the `parallel` feature.
+pub fn parse_source_lines(
+    lines: Vec<SourceLine>,
+    options: &ParseOptions,
+) -> Result<(Vec<Film>, Vec<ParseWarning>), FilmParserError> {
+    let parsed = parallel::map_records(lines, options.jobs, |line| {
+        parse_record(&line.text, line.location, options, |_| true)
+    })?;
+    Ok(collect_records(parsed))
+}
+
+/// Reads one file and keeps the films for which `keep` returns `true`, recording their source
+/// and the source of every warning. Records are checked as borrowed `FilmRef`s, so only the kept
+/// films are copied. Errors are reported like by `parse_file`.
+pub fn scan_file<F>(
+    path: &Path,
+    options: &ParseOptions,
+    mut keep: F,
+) -> Result<(Vec<Film>, Vec<ParseWarning>), FilmParserError>
+where
+    F: FnMut(&FilmRef) -> bool,
+{
+    let text = MappedText::open(path, options.encoding)?;
+    let text = text.as_str();
+    let index = LineIndex::new(text);
+    let parsed = (0..index.len())
+        .map(|line| {
+            let location = index.location(text, path, line);
+            parse_record(index.line(text, line), location, options, &mut keep)
+        })
+        .collect::<Result<Vec<ParsedRecord>, FilmParserError>>()?;
+    Ok(collect_records(parsed))
+}
+
+/// Reads and parses one file, recording the source of every film and warning. The file is
+/// mapped into memory and its records are parsed in place, on `ParseOptions::jobs` threads with
+/// the `parallel` feature.
+pub fn parse_file(
+    path: &Path,
+    options: &ParseOptions,
+) -> Result<(Vec<Film>, Vec<ParseWarning>), FilmParserError> {
+    let text = MappedText::open(path, options.encoding)?;
+    let text = text.as_str();
+    let index = LineIndex::new(text);
+    let lines: Vec<usize> = (0..index.len()).collect();
+    let parsed = parallel::map_records(lines, options.jobs, |line| {
+        let location = index.location(text, path, line);
+        parse_record(index.line(text, line), location, options, |_| true)
+    })?;
+    Ok(collect_records(parsed))
+}
+
+/// Parses the record read from `location` and keeps the film if `keep` returns `true`, setting
+/// its source. Every warning gets the location, and an error is wrapped in
+/// `FilmParserError::AtSource`. A blank record gives neither a film nor warnings.
+fn parse_record(
+    record: &str,
+    location: SourceLocation,
+    options: &ParseOptions,
+    keep: impl FnOnce(&FilmRef) -> bool,
+) -> Result<ParsedRecord, FilmParserError> {
+    if record.trim().is_empty() {
+        return Ok((None, Vec::new()));
+    }
+    let parsed = with_film_ref(record, options, |film| keep(film).then(|| film.to_owned()));
+    let (film, kinds) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return Err(FilmParserError::AtSource(location, Box::new(e))),
+    };
+    let warnings = kinds
+        .into_iter()
+        .map(|kind| ParseWarning {
+            kind,
+            location: Some(location.clone()),
+        })
+        .collect();
+    let film = film.map(|mut film| {
+        film.source = Some(location);
+        film
+    });
+    Ok((film, warnings))
+}
+
+/// Joins parsed records into the kept films and all warnings, in record order.
+fn collect_records(records: Vec<ParsedRecord>) -> (Vec<Film>, Vec<ParseWarning>) {
+    let mut films = Vec::new();
+    let mut warnings = Vec::new();
+    for (film, record_warnings) in records {
+        films.extend(film);
+        warnings.extend(record_warnings);
+    }
+    (films, warnings)
+}
+
+/// Parses the files into one list of films, in file and line order, recording the
+/// source of every film and warning.
+pub fn load_files(
+    files: &[PathBuf],
+    options: &ParseOptions,
+) -> Result<(Vec<Film>, Vec<ParseWarning>), FilmParserError> {
+    let mut films = Vec::new();
+    let mut warnings = Vec::new();
+    for file in files {
+        let (parsed, parsed_warnings) = parse_file(file, options)?;
+        films.extend(parsed);
+        warnings.extend(parsed_warnings);
+    }
+    Ok((films, warnings))
+}
+
+/// Expands the inputs and parses every file. Duplicates are merged with the policy when one is
+/// given, and with the default policy when the inputs name more than one file; the films of a
+/// single file are otherwise returned as they are.
+pub fn load_catalogue(
+    inputs: &[String],
+    policy: Option<MergePolicy>,
+    options: &ParseOptions,
+) -> Result<(Deduplicated, Vec<ParseWarning>), FilmParserError> {
+    let files = expand_inputs(inputs)?;
+    let (films, warnings) = load_files(&files, options)?;
+    let policy = match policy {
+        Some(policy) => policy,
+        None if files.len() > 1 => MergePolicy::default(),
+        None => {
+            let merged = Vec::new();
+            return Ok((Deduplicated { films, merged }, warnings));
+        }
+    };
+    Ok((dedupe::dedupe(&films, policy), warnings))
+}
+
+fn is_pattern(input: &str) -> bool {
+    input.contains(['*', '?', '['])
+}
@@ -0,0 +1,201 @@
+//! Summary statistics over a catalogue of parsed films.
+
+use crate::view::decade_label;
+use crate::{Film, FilmParserError};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Number of entries kept in the top directors, writers and stars lists by default.
+pub const DEFAULT_TOP: usize = 10;
+
+/// Width in characters of one description length bucket.
+pub const DESCRIPTION_BUCKET_WIDTH: usize = 100;
+
+/// Number of films sharing a key, e.g. a genre or a director.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Count {
+    /// The counted value.
+    pub key: String,
+    /// Number of films with this value.
+    pub count: usize,
+}
+
+/// Distribution of description lengths, measured in characters.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LengthDistribution {
+    /// Length of the shortest description.
+    pub min: usize,
+    /// Length of the longest description.
+    pub max: usize,
+    /// Average description length.
+    pub mean: f64,
+    /// Median description length.
+    pub median: f64,
+    /// Number of descriptions per length range, e.g. `100-199`.
+    pub buckets: Vec<Count>,
+}
+
+/// Statistics computed from a list of films.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CatalogueStats {
+    /// Total number of films.
+    pub film_count: usize,
+    /// Films per genre, most frequent first.
+    pub genres: Vec<Count>,
+    /// Films per decade, in chronological order.
+    pub decades: Vec<Count>,
+    /// Films per release year, in chronological order.
+    pub years: Vec<Count>,
+    /// Directors with the most films.
+    pub top_directors: Vec<Count>,
+    /// Writers with the most films.
+    pub top_writers: Vec<Count>,
+    /// Stars appearing in the most films.
+    pub top_stars: Vec<Count>,
+    /// Average number of stars per film.
+    pub average_cast_size: f64,
+    /// Distribution of description lengths.
+    pub description_lengths: LengthDistribution,
+    /// Share of films written by their director, from 0 to 1.
+    pub director_writer_share: f64,
+}
+
+impl CatalogueStats {
+    /// Computes statistics with the default number of top entries.
+    pub fn compute(films: &[Film]) -> Self {
+        Self::compute_with_top(films, DEFAULT_TOP)
+    }
+
+    /// Computes statistics keeping `top` entries in the top directors, writers and stars lists.
+    pub fn compute_with_top(films: &[Film], top: usize) -> Self {
+        let film_count = films.len();
+
+        let genres = by_frequency(count_keys(films.iter().flat_map(|f| distinct(&f.genre))));
+        let decades = chronological(
+            count_years(films.iter().map(|f| f.year / 10 * 10)),
+            decade_label,
+        );
+        let years = chronological(count_years(films.iter().map(|f| f.year)), |year| {
+            year.to_string()
+        });
+
+        let mut top_directors = by_frequency(count_keys(films.iter().map(|f| f.director.clone())));
+        top_directors.truncate(top);
+        let mut top_writers = by_frequency(count_keys(films.iter().map(|f| f.writer.clone())));
+        top_writers.truncate(top);
+        let mut top_stars = by_frequency(count_keys(films.iter().flat_map(|f| distinct(&f.stars))));
+        top_stars.truncate(top);
+
+        let total_cast: usize = films.iter().map(|f| f.stars.len()).sum();
+        let director_writer_films = films
+            .iter()
+            .filter(|f| f.director.trim().to_lowercase() == f.writer.trim().to_lowercase())
+            .count();
+
+        CatalogueStats {
+            film_count,
+            genres,
+            decades,
+            years,
+            top_directors,
+            top_writers,
+            top_stars,
+            average_cast_size: ratio(total_cast, film_count),
+            description_lengths: LengthDistribution::compute(
+                films
+                    .iter()
+                    .map(|f| f.description.chars().count())
+                    .collect(),
+            ),
+            director_writer_share: ratio(director_writer_films, film_count),
+        }
+    }
+
+    /// Serializes the statistics as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, FilmParserError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| FilmParserError::SerializationError(e.to_string()))
+    }
+}
+
+impl LengthDistribution {
+    fn compute(mut lengths: Vec<usize>) -> Self {
+        lengths.sort_unstable();
+        let median = match lengths.len() {
+            0 => 0.0,
+            n if n % 2 == 1 => lengths[n / 2] as f64,
+            n => (lengths[n / 2 - 1] + lengths[n / 2]) as f64 / 2.0,
+        };
+
+        let mut buckets: Vec<Count> = Vec::new();
+        for length in &lengths {
+            let start = length / DESCRIPTION_BUCKET_WIDTH * DESCRIPTION_BUCKET_WIDTH;
+            let key = format!("{}-{}", start, start + DESCRIPTION_BUCKET_WIDTH - 1);
+            match buckets.last_mut() {
+                Some(bucket) if bucket.key == key => bucket.count += 1,
+                _ => buckets.push(Count { key, count: 1 }),
+            }
+        }
+
+        LengthDistribution {
+            min: lengths.first().copied().unwrap_or(0),
+            max: lengths.last().copied().unwrap_or(0),
+            mean: ratio(lengths.iter().sum(), lengths.len()),
+            median,
+            buckets,
+        }
+    }
+}
+
+pub(crate) fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+fn count_keys(keys: impl Iterator<Item = String>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for key in keys {
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Most frequent first; ties are ordered by key.
+fn by_frequency(counts: HashMap<String, usize>) -> Vec<Count> {
+    let mut counts: Vec<Count> = counts
+        .into_iter()
+        .map(|(key, count)| Count { key, count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    counts
+}
+
+/// The items of a list without repetitions, so a film counts once per genre or star.
+fn distinct(items: &[String]) -> Vec<String> {
+    let mut items = items.to_vec();
+    items.sort();
+    items.dedup();
+    items
+}
+
+fn count_years(years: impl Iterator<Item = u32>) -> BTreeMap<u32, usize> {
+    let mut counts = BTreeMap::new();
+    for year in years {
+        *counts.entry(year).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Oldest first, labelled with `label` only after sorting so that e.g. `999` comes before `2000`.
+fn chronological(counts: BTreeMap<u32, usize>, label: impl Fn(u32) -> String) -> Vec<Count> {
+    counts
+        .into_iter()
+        .map(|(year, count)| Count {
+            key: label(year),
+            count,
+        })
+        .collect()
+}
@@ -0,0 +1,170 @@
+//! Synthetic catalogues for benchmarks and load tests.
+//!
+//! A `SyntheticCatalogue` generates any number of well-formed films from a seed, so the same
+//! settings always give the same catalogue. Names mix several scripts and diacritics, cast lists
+//! can be made as long as needed, and every title is unique so no film is merged as a duplicate.
+
+use crate::options::KNOWN_GENRES;
+use crate::Film;
+
+const FIRST_NAMES: [&str; 16] = [
+    "Zoë",
+    "Björn",
+    "Søren",
+    "Chloé",
+    "Jiří",
+    "Łukasz",
+    "Оксана",
+    "Тарас",
+    "Ólafur",
+    "José",
+    "François",
+    "Ayşe",
+    "Nguyễn",
+    "Παναγιώτης",
+    "Ji-woo",
+    "Mária",
+];
+
+const LAST_NAMES: [&str; 16] = [
+    "Åkesson",
+    "Dvořák",
+    "Kowalczyk",
+    "Шевченко",
+    "Müller",
+    "García Márquez",
+    "Øvergård",
+    "Yılmaz",
+    "O'Connor",
+    "Ferreira",
+    "Papadopoulos",
+    "佐藤",
+    "陳",
+    "Lefèvre",
+    "Kovács",
+    "Nakamura",
+];
+
+const TITLE_WORDS: [&str; 16] = [
+    "Silent", "Harbour", "Crimson", "Тінь", "Night", "Garden", "Última", "Mirror", "Winter",
+    "Échos", "Signal", "Forêt", "Lantern", "夜", "Glass", "River",
+];
+
+const DESCRIPTION_WORDS: [&str; 16] = [
+    "a", "stranger", "returns", "to", "the", "village", "where", "every", "secret", "is", "buried",
+    "under", "snow", "and", "old", "songs",
+];
+
+/// Settings of a generated catalogue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyntheticCatalogue {
+    /// Number of films.
+    pub films: usize,
+    /// Number of stars of every film, at least one.
+    pub cast: usize,
+    /// Seed of the generator.
+    pub seed: u64,
+}
+
+impl Default for SyntheticCatalogue {
+    fn default() -> Self {
+        SyntheticCatalogue {
+            films: 10_000,
+            cast: 12,
+            seed: 1,
+        }
+    }
+}
+
+impl SyntheticCatalogue {
+    /// A catalogue of the given number of films with the default cast size and seed.
+    pub fn new(films: usize) -> Self {
+        SyntheticCatalogue {
+            films,
+            ..Self::default()
+        }
+    }
+
+    /// Gives every film `cast` stars.
+    pub fn with_cast(mut self, cast: usize) -> Self {
+        self.cast = cast;
+        self
+    }
+
+    /// Uses another seed, which gives another catalogue.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Generates the films.
+    pub fn generate(&self) -> Vec<Film> {
+        let mut random = Random::new(self.seed);
+        (0..self.films)
+            .map(|index| {
+                let title = format!(
+                    "{} {} {}",
+                    random.pick(&TITLE_WORDS),
+                    random.pick(&TITLE_WORDS),
+                    index + 1
+                );
+                let year = 1920 + random.below(106) as u32;
+                let director = random.name();
+                let writer = if random.below(3) == 0 {
+                    director.clone()
+                } else {
+                    random.name()
+                };
+                let genres = 1 + random.below(3);
+                let mut genre: Vec<String> = Vec::new();
+                while genre.len() < genres {
+                    let picked = random.pick(&KNOWN_GENRES).to_string();
+                    if !genre.contains(&picked) {
+                        genre.push(picked);
+                    }
+                }
+                let stars = (0..self.cast.max(1)).map(|_| random.name()).collect();
+                let words = 8 + random.below(24);
+                let description = (0..words)
+                    .map(|_| random.pick(&DESCRIPTION_WORDS))
+                    .collect::<Vec<&str>>()
+                    .join(" ")
+                    + ".";
+                Film::new(title, year, director, writer, genre, stars, description)
+            })
+            .collect()
+    }
+
+    /// Generates the films as record lines in the input format.
+    pub fn record_lines(&self) -> Vec<String> {
+        self.generate().iter().map(Film::to_record_line).collect()
+    }
+}
+
+/// A small xorshift generator, good enough to vary the films and stable across platforms.
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        Random(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, words: &[&'a str]) -> &'a str {
+        words[self.below(words.len())]
+    }
+
+    fn name(&mut self) -> String {
+        format!("{} {}", self.pick(&FIRST_NAMES), self.pick(&LAST_NAMES))
+    }
+}
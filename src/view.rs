@@ -0,0 +1,165 @@
+//! Sorting, grouping and field selection for lists of parsed films.
+
+use crate::{Film, FilmField, FilmParserError};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// A field to sort by and the sort direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    /// The field the films are compared by.
+    pub field: FilmField,
+    /// Sort from the largest to the smallest value.
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// Parses a comma-separated list of sort keys, e.g. `year,-title`.
+    /// A leading `-` sorts the field in descending order.
+    pub fn parse_list(input: &str) -> Result<Vec<SortKey>, FilmParserError> {
+        input
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(|key| match key.strip_prefix('-') {
+                Some(name) => Ok(SortKey {
+                    field: name.parse()?,
+                    descending: true,
+                }),
+                None => Ok(SortKey {
+                    field: key.trim_start_matches('+').parse()?,
+                    descending: false,
+                }),
+            })
+            .collect()
+    }
+
+    /// Compares two films by this key.
+    pub fn compare(&self, a: &Film, b: &Film) -> Ordering {
+        let ordering = match self.field {
+            FilmField::Year => a.year.cmp(&b.year),
+            field => {
+                let a_value = field.values(a).join(", ").to_lowercase();
+                let b_value = field.values(b).join(", ").to_lowercase();
+                a_value.cmp(&b_value)
+            }
+        };
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Sorts films by the given keys. Films that compare equal keep their input order.
+pub fn sort_films(films: &mut [Film], keys: &[SortKey]) {
+    films.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+/// The property films are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// One group per director.
+    Director,
+    /// One group per writer.
+    Writer,
+    /// One group per genre; a film appears in each of its genres.
+    Genre,
+    /// One group per star; a film appears once for each of its stars.
+    Star,
+    /// One group per release year.
+    Year,
+    /// One group per decade, e.g. `2010s`.
+    Decade,
+}
+
+impl GroupBy {
+    /// Returns the group keys of a film.
+    pub fn keys(self, film: &Film) -> Vec<String> {
+        match self {
+            GroupBy::Director => vec![film.director.clone()],
+            GroupBy::Writer => vec![film.writer.clone()],
+            GroupBy::Genre => film.genre.clone(),
+            GroupBy::Star => film.stars.clone(),
+            GroupBy::Year => vec![film.year.to_string()],
+            GroupBy::Decade => vec![decade_label(film.year)],
+        }
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = FilmParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "director" => Ok(GroupBy::Director),
+            "writer" => Ok(GroupBy::Writer),
+            "genre" | "genres" => Ok(GroupBy::Genre),
+            "star" | "stars" => Ok(GroupBy::Star),
+            "year" => Ok(GroupBy::Year),
+            "decade" => Ok(GroupBy::Decade),
+            _ => Err(FilmParserError::InvalidOption(format!(
+                "group-by {}, expected director, writer, genre, star, year or decade",
+                s
+            ))),
+        }
+    }
+}
+
+/// Returns the decade of a year as a label, e.g. `2010s` for 2016.
+pub fn decade_label(year: u32) -> String {
+    format!("{}s", year / 10 * 10)
+}
+
+/// Groups films by the given property.
+/// Groups are ordered by key, years and decades by number, and films keep their order within a
+/// group.
+pub fn group_films(films: &[Film], group_by: GroupBy) -> Vec<(String, Vec<&Film>)> {
+    let mut groups: BTreeMap<String, Vec<&Film>> = BTreeMap::new();
+    for film in films {
+        let mut keys = group_by.keys(film);
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            groups.entry(key).or_default().push(film);
+        }
+    }
+    let mut groups: Vec<(String, Vec<&Film>)> = groups.into_iter().collect();
+    if matches!(group_by, GroupBy::Year | GroupBy::Decade) {
+        // As text "999" sorts after "2016".
+        groups.sort_by_key(|(key, _)| key.trim_end_matches('s').parse::<u32>().unwrap_or(0));
+    }
+    groups
+}
+
+/// Parses a comma-separated list of field names, e.g. `title,year,director`.
+pub fn parse_field_list(input: &str) -> Result<Vec<FilmField>, FilmParserError> {
+    let fields = input
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<FilmField>, FilmParserError>>()?;
+    if fields.is_empty() {
+        return Err(FilmParserError::InvalidOption(
+            "fields, at least one field is required".to_string(),
+        ));
+    }
+    Ok(fields)
+}
+
+/// Formats the selected fields of a film as `Key: value` lines, in the given order.
+/// List fields are joined with `, `.
+pub fn format_film_fields(film: &Film, fields: &[FilmField]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("{}: {}\n", field.key(), field.values(film).join(", ")))
+        .collect()
+}
@@ -0,0 +1,60 @@
+use film_parser::chart::*;
+use film_parser::stats::Count;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(key: &str, count: usize) -> Count {
+        Count {
+            key: key.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn test_bar_uses_partial_blocks() {
+        assert_eq!(bar(4, 4, 4), "████");
+        assert_eq!(bar(1, 4, 2), "▌");
+        assert_eq!(bar(0, 4, 4), "");
+        assert_eq!(bar(1, 1000, 4), "▏");
+    }
+
+    #[test]
+    fn test_bar_chart_fits_width() {
+        colored::control::set_override(false);
+        let rows = vec![
+            count("Horror", 96),
+            count("A very long genre name that is shortened", 12),
+        ];
+        let chart = render_bar_chart("Genres", &rows, 40);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines[0], "Genres");
+        assert!(lines[1].starts_with("Horror "));
+        assert!(lines[1].ends_with(" 96"));
+        assert!(lines[2].contains('…'));
+        assert!(lines.iter().all(|line| line.chars().count() <= 40));
+    }
+
+    #[test]
+    fn test_histogram() {
+        colored::control::set_override(false);
+        let rows = vec![count("2019", 2), count("2020", 4)];
+        let chart = render_histogram("Films per year", &rows, 2, 80);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines[1], "     ████");
+        assert_eq!(lines[2], "████ ████");
+        assert_eq!(lines[3], "2    4");
+        assert_eq!(lines[4], "2019 2020");
+    }
+
+    #[test]
+    fn test_histogram_falls_back_to_bar_chart() {
+        colored::control::set_override(false);
+        let rows: Vec<Count> = (2000..2030)
+            .map(|year| count(&year.to_string(), 1))
+            .collect();
+        let chart = render_histogram("Films per year", &rows, 4, 40);
+        assert_eq!(chart.lines().count(), rows.len() + 1);
+    }
+}
@@ -0,0 +1,80 @@
+//! Fixtures shared by the integration tests.
+// Each test crate compiles this module and uses only part of it.
+#![allow(dead_code)]
+
+use film_parser::Film;
+use std::fs;
+use std::path::PathBuf;
+
+/// Builds a test film, with placeholder values for the fields a test does not set.
+pub struct FilmBuilder {
+    film: Film,
+}
+
+/// Starts a film with the given title, released in 2020, with one genre, one star and a
+/// placeholder director, writer and description.
+pub fn film(title: &str) -> FilmBuilder {
+    FilmBuilder {
+        film: Film::new(
+            title.to_string(),
+            2020,
+            "Some_Director".to_string(),
+            "Some_Writer".to_string(),
+            vec!["Horror".to_string()],
+            vec!["Some_Actor".to_string()],
+            "Some_Description.".to_string(),
+        ),
+    }
+}
+
+impl FilmBuilder {
+    pub fn year(mut self, year: u32) -> Self {
+        self.film.year = year;
+        self
+    }
+
+    /// Sets the director, who is also credited as the writer unless `writer` is called after.
+    pub fn director(mut self, director: &str) -> Self {
+        self.film.director = director.to_string();
+        self.film.writer = director.to_string();
+        self
+    }
+
+    pub fn writer(mut self, writer: &str) -> Self {
+        self.film.writer = writer.to_string();
+        self
+    }
+
+    pub fn genre(mut self, genre: &[&str]) -> Self {
+        self.film.genre = genre.iter().map(|genre| genre.to_string()).collect();
+        self
+    }
+
+    pub fn stars(mut self, stars: &[&str]) -> Self {
+        self.film.stars = stars.iter().map(|star| star.to_string()).collect();
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.film.description = description.to_string();
+        self
+    }
+
+    pub fn build(self) -> Film {
+        self.film
+    }
+}
+
+/// Creates a directory for `name` under the system temporary directory, unique to this process.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("film_parser_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes `contents` to a file named `name` in a temporary directory of this process.
+pub fn temp_file(name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+    let path = temp_dir("files").join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::*;
+use film_parser::compression::*;
+use film_parser::sources::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn sample_films() -> Vec<Film> {
+        vec![film("Raw")
+            .year(2016)
+            .director("Julia Ducournau")
+            .genre(&["Drama", "Horror"])
+            .stars(&["Garance Marillier"])
+            .description("A vet student.")
+            .build()]
+    }
+
+    #[test]
+    fn test_gzip_records_round_trip() {
+        let dir = temp_dir("compression_gzip");
+        let path = dir.join("films.txt.gz");
+        write_catalogue(&sample_films(), path.to_str().unwrap()).unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        assert_eq!(Compression::detect(&bytes), Compression::Gzip);
+        let (films, _) = parse_file(&path, &ParseOptions::default()).unwrap();
+        assert_eq!(films[0].title, "Raw");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_zstd_json_lines_are_written() {
+        let dir = temp_dir("compression_zstd");
+        let path = dir.join("out.jsonl.zst");
+        write_catalogue(&sample_films(), path.to_str().unwrap()).unwrap();
+
+        let content = String::from_utf8(read_file(&path).unwrap()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(value["title"], "Raw");
+        assert_eq!(value["genre"][1], "Horror");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compression_is_detected_from_magic_bytes() {
+        let dir = temp_dir("compression_magic");
+        let compressed = dir.join("films.txt.zst");
+        write_catalogue(&sample_films(), compressed.to_str().unwrap()).unwrap();
+        let renamed = dir.join("films.txt");
+        fs::rename(&compressed, &renamed).unwrap();
+
+        let lines = read_lines(renamed.to_str().unwrap()).unwrap();
+        assert!(lines[0].starts_with("Title: Raw; Year: 2016"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compressed_catalogues_are_found_in_directories() {
+        let dir = temp_dir("compression_walk");
+        write_catalogue(&sample_films(), dir.join("a.txt.gz").to_str().unwrap()).unwrap();
+        fs::write(dir.join("notes.md.gz"), b"").unwrap();
+
+        let files = expand_inputs(&[dir.display().to_string()]).unwrap();
+        assert_eq!(files, vec![dir.join("a.txt.gz")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compression_from_path() {
+        assert_eq!(
+            Compression::from_path(Path::new("a.txt.gz")),
+            Compression::Gzip
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("a.jsonl.zst")),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("a.txt")),
+            Compression::None
+        );
+        assert_eq!(
+            uncompressed_path(Path::new("out.jsonl.zst")),
+            Path::new("out.jsonl")
+        );
+        assert_eq!(
+            uncompressed_path(Path::new("data/v1.2/out.txt.gz")),
+            Path::new("data/v1.2/out.txt")
+        );
+        assert_eq!(uncompressed_path(Path::new("a.txt")), Path::new("a.txt"));
+    }
+}
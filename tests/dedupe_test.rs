@@ -0,0 +1,106 @@
+mod common;
+
+use common::*;
+use film_parser::dedupe::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_films() -> Vec<Film> {
+        vec![
+            film("The Witch")
+                .year(2015)
+                .director("Robert Eggers")
+                .stars(&["Anya Taylor-Joy"])
+                .description("A family in 1630s New England.")
+                .build(),
+            film("Raw")
+                .year(2015)
+                .director("Julia Ducournau")
+                .stars(&["Garance Marillier"])
+                .description("A vet student.")
+                .build(),
+            film("the  witch.")
+                .year(2015)
+                .director("robert eggers")
+                .stars(&["anya taylor-joy", "Ralph Ineson"])
+                .description("A family in 1630s New England is torn apart by witchcraft.")
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn test_find_duplicates_normalises_title_and_director() {
+        let groups = find_duplicates(&sample_films());
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].records, vec![0, 2]);
+        assert_eq!(groups[0].key, "the witch|2015|robert eggers");
+    }
+
+    #[test]
+    fn test_different_year_is_not_a_duplicate() {
+        let mut films = sample_films();
+        films[2].year = 2016;
+
+        assert!(find_duplicates(&films).is_empty());
+    }
+
+    #[test]
+    fn test_default_policy_unions_lists_and_prefers_longer_description() {
+        let films = sample_films();
+        let merged = merge_group(&films, &find_duplicates(&films)[0], MergePolicy::default());
+
+        assert_eq!(merged.film.title, "The Witch");
+        assert_eq!(merged.film.stars, vec!["Anya Taylor-Joy", "Ralph Ineson"]);
+        assert_eq!(merged.film.description, films[2].description);
+
+        let sources = |field| {
+            merged
+                .provenance
+                .iter()
+                .find(|source| source.field == field)
+                .unwrap()
+                .records
+                .clone()
+        };
+        assert_eq!(sources(FilmField::Title), vec![0]);
+        assert_eq!(sources(FilmField::Stars), vec![0, 2]);
+        assert_eq!(sources(FilmField::Genre), vec![0]);
+        assert_eq!(sources(FilmField::Description), vec![2]);
+    }
+
+    #[test]
+    fn test_first_policy_keeps_first_record() {
+        let films = sample_films();
+        let policy = MergePolicy::parse_list("first").unwrap();
+        let merged = merge_group(&films, &find_duplicates(&films)[0], policy);
+
+        assert_eq!(merged.film.stars, films[0].stars);
+        assert_eq!(merged.film.description, films[0].description);
+        assert!(merged.provenance.iter().all(|source| source.records == [0]));
+    }
+
+    #[test]
+    fn test_dedupe_keeps_position_of_first_record() {
+        let deduplicated = dedupe(&sample_films(), MergePolicy::default());
+        let titles: Vec<&str> = deduplicated
+            .films
+            .iter()
+            .map(|film| film.title.as_str())
+            .collect();
+
+        assert_eq!(titles, vec!["The Witch", "Raw"]);
+        assert_eq!(deduplicated.merged.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        assert!(matches!(
+            MergePolicy::parse_list("union,newest"),
+            Err(FilmParserError::InvalidOption(_))
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use common::*;
+use film_parser::diff::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_catalogues() {
+        let films = vec![film("Midsommar")
+            .year(2019)
+            .stars(&["Florence Pugh"])
+            .description("A festival.")
+            .build()];
+        assert!(CatalogueDiff::compute(&films, &films).is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed_films() {
+        let old = vec![
+            film("Hereditary")
+                .year(2018)
+                .stars(&["Toni Collette"])
+                .description("A family.")
+                .build(),
+            film("Midsommar")
+                .year(2019)
+                .stars(&["Florence Pugh"])
+                .description("A festival.")
+                .build(),
+        ];
+        let new = vec![
+            film("Midsommar")
+                .year(2019)
+                .stars(&["Florence Pugh"])
+                .description("A festival.")
+                .build(),
+            film("Beau Is Afraid")
+                .year(2023)
+                .stars(&["Joaquin Phoenix"])
+                .description("A journey.")
+                .build(),
+        ];
+        let catalogue_diff = CatalogueDiff::compute(&old, &new);
+
+        assert_eq!(catalogue_diff.removed[0].title, "Hereditary");
+        assert_eq!(catalogue_diff.added[0].title, "Beau Is Afraid");
+        assert!(catalogue_diff.changed.is_empty());
+    }
+
+    #[test]
+    fn test_same_title_different_year_is_a_different_film() {
+        let old = vec![film("Suspiria")
+            .year(1977)
+            .stars(&["Jessica Harper"])
+            .description("A dance academy.")
+            .build()];
+        let new = vec![film("Suspiria")
+            .year(2018)
+            .stars(&["Dakota Johnson"])
+            .description("A dance academy.")
+            .build()];
+        let catalogue_diff = CatalogueDiff::compute(&old, &new);
+
+        assert_eq!(catalogue_diff.added.len(), 1);
+        assert_eq!(catalogue_diff.removed.len(), 1);
+    }
+
+    #[test]
+    fn test_field_level_changes() {
+        let old = vec![film("Midsommar")
+            .year(2019)
+            .stars(&["Florence Pugh", "Jack Reynor"])
+            .description("A festival.")
+            .build()];
+        let new = vec![film("midsommar")
+            .year(2019)
+            .stars(&["Florence Pugh", "Will Poulter"])
+            .description("A summer festival.")
+            .build()];
+        let catalogue_diff = CatalogueDiff::compute(&old, &new);
+
+        assert_eq!(catalogue_diff.changed.len(), 1);
+        assert_eq!(
+            catalogue_diff.changed[0].changes,
+            vec![
+                FieldChange::Value {
+                    field: FilmField::Title,
+                    old: "Midsommar".to_string(),
+                    new: "midsommar".to_string(),
+                },
+                FieldChange::List {
+                    field: FilmField::Stars,
+                    added: vec!["Will Poulter".to_string()],
+                    removed: vec!["Jack Reynor".to_string()],
+                },
+                FieldChange::Value {
+                    field: FilmField::Description,
+                    old: "A festival.".to_string(),
+                    new: "A summer festival.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_output() {
+        let old = vec![film("Midsommar")
+            .year(2019)
+            .stars(&["Florence Pugh"])
+            .description("A festival.")
+            .build()];
+        let new = vec![film("Midsommar")
+            .year(2019)
+            .stars(&["Will Poulter"])
+            .description("A festival.")
+            .build()];
+        let json = CatalogueDiff::compute(&old, &new).to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let change = &value["changed"][0];
+        assert_eq!(change["title"], "Midsommar");
+        assert_eq!(change["changes"][0]["kind"], "list");
+        assert_eq!(change["changes"][0]["field"], "stars");
+        assert_eq!(change["changes"][0]["added"][0], "Will Poulter");
+    }
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::*;
+use film_parser::encoding::*;
+use film_parser::sources::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const RECORD: &str = "Title: Вій; Year: 1967; Director: Костянтин Єршов; Writer: Олександр Птушко; Genre: [Horror]; Stars: [Леонід Куравльов]; Description: A seminarist keeps vigil.";
+
+    #[test]
+    fn test_utf8_bom_is_stripped() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(RECORD.as_bytes());
+        let path = temp_file("bom.txt", &bytes);
+
+        let (films, _) = parse_file(&path, &ParseOptions::default()).unwrap();
+        assert_eq!(films[0].title, "Вій");
+        assert_eq!(read_lines(path.to_str().unwrap()).unwrap()[0], RECORD);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_utf16_is_detected_from_its_bom() {
+        let mut little_endian = vec![0xFF, 0xFE];
+        let mut big_endian = vec![0xFE, 0xFF];
+        for unit in RECORD.encode_utf16() {
+            little_endian.extend_from_slice(&unit.to_le_bytes());
+            big_endian.extend_from_slice(&unit.to_be_bytes());
+        }
+
+        assert_eq!(decode(&little_endian, None).unwrap(), RECORD);
+        assert_eq!(decode(&big_endian, None).unwrap(), RECORD);
+    }
+
+    #[test]
+    fn test_legacy_files_are_transcoded() {
+        let windows_1251 = encoding_for_label("windows-1251").unwrap();
+        let (bytes, _, _) = windows_1251.encode(RECORD);
+        let path = temp_file("cp1251.txt", &bytes);
+
+        let options = ParseOptions::default().with_encoding(windows_1251);
+        let (films, _) = parse_file(&path, &options).unwrap();
+        assert_eq!(films[0].director, "Костянтин Єршов");
+        assert_eq!(films[0].source.as_ref().unwrap().byte_span, 0..RECORD.len());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_utf8_suggests_an_encoding() {
+        let path = temp_file("latin1.txt", b"Title: Am\xe9lie; Year: 2001");
+
+        match parse_file(&path, &ParseOptions::default()) {
+            Err(FilmParserError::FileReadingError(message)) => {
+                assert!(message.contains("--encoding"))
+            }
+            other => panic!("expected a reading error, got {:?}", other),
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_encoding_labels() {
+        assert_eq!(encoding_for_label("latin1").unwrap().name(), "windows-1252");
+        assert_eq!(encoding_for_label(" UTF-16LE ").unwrap().name(), "UTF-16LE");
+        assert!(matches!(
+            encoding_for_label("klingon"),
+            Err(FilmParserError::InvalidOption(_))
+        ));
+    }
+}
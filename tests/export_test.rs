@@ -0,0 +1,70 @@
+mod common;
+
+use common::*;
+use film_parser::export::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> CollaborationGraph {
+        CollaborationGraph::build(&[
+            film("Hereditary")
+                .director("Ari Aster")
+                .stars(&["Toni Collette", "Alex Wolff"])
+                .build(),
+            film("Beau Is Afraid")
+                .director("Ari Aster")
+                .stars(&["Joaquin Phoenix", "Alex Wolff"])
+                .build(),
+            film("Knives \"Out\"")
+                .director("Rian Johnson")
+                .stars(&["Toni Collette"])
+                .build(),
+        ])
+    }
+
+    #[test]
+    fn test_dot_with_films() {
+        let dot = to_dot(&sample_graph(), &ExportOptions::default());
+        assert!(dot.starts_with("graph collaborations {\n"));
+        assert!(dot.contains("[label=\"Knives \\\"Out\\\" (2020)\", shape=box, type=\"film\"]"));
+        assert!(dot.contains("[label=\"Ari Aster\", shape=ellipse, type=\"director,writer\"]"));
+        assert_eq!(dot.matches(" -- ").count(), 11);
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_people_only_with_min_shared_films() {
+        let options = ExportOptions {
+            node_types: NodeType::parse_list("director,star").expect("Failed to parse node types"),
+            min_shared_films: 2,
+        };
+        let dot = to_dot(&sample_graph(), &options);
+        assert!(!dot.contains("shape=box"));
+        assert_eq!(dot.matches(" -- ").count(), 1);
+        assert!(dot.contains("label=\"2 shared films\", weight=2"));
+        assert!(dot.contains("Alex Wolff"));
+        assert!(!dot.contains("Joaquin Phoenix"));
+    }
+
+    #[test]
+    fn test_graphml_is_escaped() {
+        let xml = export(
+            &sample_graph(),
+            "graphml".parse().expect("Failed to parse format"),
+            &ExportOptions::default(),
+        );
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<data key=\"label\">Knives &quot;Out&quot; (2020)</data>"));
+        assert_eq!(xml.matches("<edge ").count(), 11);
+        assert!(xml.trim_end().ends_with("</graphml>"));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        assert!(NodeType::parse_list("director,producer").is_err());
+        assert!("svg".parse::<ExportFormat>().is_err());
+    }
+}
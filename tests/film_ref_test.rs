@@ -0,0 +1,89 @@
+use film_parser::film_ref::*;
+use film_parser::sources::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const RECORD: &str = "Title: Raw; Year: 2016; Director: Julia Ducournau; Writer: Julia Ducournau; Genre: [Drama, Horror]; Stars: [Garance Marillier, Ella Rumpf]; Description: A vet student.";
+
+    fn borrows_from(value: &str, line: &str) -> bool {
+        let range = line.as_bytes().as_ptr_range();
+        range.contains(&value.as_ptr())
+    }
+
+    #[test]
+    fn test_fields_borrow_from_the_line() {
+        let (film, warnings) = FilmRef::parse(RECORD, &ParseOptions::strict()).unwrap();
+
+        assert!(warnings.is_empty());
+        assert_eq!(film.title, "Raw");
+        assert_eq!(film.year, 2016);
+        assert_eq!(film.genre, vec!["Drama", "Horror"]);
+        assert!(borrows_from(film.title, RECORD));
+        assert!(borrows_from(film.stars[1], RECORD));
+        assert!(borrows_from(film.description, RECORD));
+    }
+
+    #[test]
+    fn test_to_owned_matches_parsed_film() {
+        let (film, _) = FilmRef::parse(RECORD, &ParseOptions::strict()).unwrap();
+        let parsed = parse_film_lines(vec![RECORD.to_string()]).unwrap();
+
+        assert_eq!(format!("{:?}", film.to_owned()), format!("{:?}", parsed[0]));
+        assert_eq!(parsed[0].as_film_ref(), film);
+    }
+
+    #[test]
+    fn test_errors_and_warnings_match_film() {
+        let broken = "Title: Raw; Year: 2016";
+        assert!(FilmRef::parse(broken, &ParseOptions::strict()).is_err());
+        assert!(parse_film_lines(vec![broken.to_string()]).is_err());
+
+        let relaxed = "title:Raw; Year: 2016; Genre: [Drama, , Horror]";
+        let (film, warnings) = FilmRef::parse(relaxed, &ParseOptions::lenient()).unwrap();
+        let (_, expected) =
+            parse_film_lines_with_warnings(vec![relaxed.to_string()], &ParseOptions::lenient())
+                .unwrap();
+        let expected: Vec<WarningKind> = expected.into_iter().map(|w| w.kind).collect();
+        assert_eq!(film.genre, vec!["Drama", "Horror"]);
+        assert_eq!(warnings, expected);
+    }
+
+    #[test]
+    fn test_text_values() {
+        let (film, _) = FilmRef::parse(RECORD, &ParseOptions::strict()).unwrap();
+
+        assert_eq!(film.text_values(FilmField::Title), ["Raw"]);
+        assert_eq!(
+            film.text_values(FilmField::Stars),
+            ["Garance Marillier", "Ella Rumpf"]
+        );
+        assert!(film.text_values(FilmField::Year).is_empty());
+        let query = Query::parse("genre = horror and star ~ rumpf").unwrap();
+        assert!(query.matches_ref(&film));
+    }
+
+    #[test]
+    fn test_scan_file_copies_only_kept_films() {
+        let path = std::env::temp_dir().join(format!("film_ref_scan_{}.txt", std::process::id()));
+        let other = RECORD.replace("Raw", "Titane").replace("2016", "2021");
+        fs::write(&path, format!("{}\n\n{}\n", RECORD, other)).unwrap();
+
+        let mut scanned = 0;
+        let (films, warnings) = scan_file(&path, &ParseOptions::strict(), |film| {
+            scanned += 1;
+            film.year > 2020
+        })
+        .unwrap();
+
+        assert_eq!(scanned, 2);
+        assert!(warnings.is_empty());
+        assert_eq!(films.len(), 1);
+        assert_eq!(films[0].title, "Titane");
+        assert_eq!(films[0].source.as_ref().unwrap().line, 3);
+        fs::remove_file(&path).unwrap();
+    }
+}
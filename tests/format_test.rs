@@ -0,0 +1,80 @@
+mod common;
+
+use common::*;
+use film_parser::format::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const CANONICAL: &str = "Title: Raw; Year: 2016; Director: Julia Ducournau; Writer: Julia Ducournau; Genre: [Drama, Horror]; Stars: [Garance Marillier, Ella Rumpf]; Description: A vet student.\n";
+
+    #[test]
+    fn test_canonical_file_is_unchanged() {
+        let path = temp_file("canonical.txt", CANONICAL);
+        let formatted = format_file(&path, &FormatOptions::default()).unwrap();
+
+        assert!(!formatted.changed);
+        assert_eq!(formatted.content, CANONICAL);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_separators_values_and_genre_casing_are_normalised() {
+        let path = temp_file(
+            "messy.txt",
+            "Title: Raw ;   Year: 2016;Director: Julia Ducournau; Writer: Julia Ducournau;  Genre: [drama,   HORROR]; Stars: [Garance Marillier ,Ella Rumpf]; Description: A vet student. \n\n",
+        );
+        let formatted = format_file(&path, &FormatOptions::default()).unwrap();
+
+        assert!(formatted.changed);
+        assert_eq!(formatted.content, CANONICAL);
+        assert!(formatted.warnings.is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_known_genres_keep_their_spelling() {
+        let film = film("Raw")
+            .genre(&["tv movie", "SCIENCE fiction", "k-drama"])
+            .build();
+
+        assert_eq!(
+            canonical_film(&film).genre,
+            vec!["TV Movie", "Science Fiction", "K-Drama"]
+        );
+    }
+
+    #[test]
+    fn test_records_are_sorted() {
+        let films = vec![
+            film("Us").year(2019).genre(&["sci-fi"]).build(),
+            film("Get Out").year(2017).genre(&["horror"]).build(),
+        ];
+        let options = FormatOptions {
+            sort_keys: view::SortKey::parse_list("title").unwrap(),
+            ..FormatOptions::default()
+        };
+        let content = format_films(&films, &options);
+        let lines: Vec<&str> = content.lines().collect();
+
+        assert!(lines[0].starts_with("Title: Get Out; Year: 2017"));
+        assert!(lines[1].contains("Genre: [Sci-Fi]"));
+    }
+
+    #[test]
+    fn test_ignored_text_is_rejected() {
+        let path = temp_file(
+            "ignored.txt",
+            CANONICAL.replace("; Description: A vet student.", "; she eats meat."),
+        );
+
+        match format_file(&path, &FormatOptions::default()) {
+            Err(FilmParserError::AtSource(location, _)) => assert_eq!(location.line, 1),
+            other => panic!("expected an error at line 1, got {:?}", other),
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
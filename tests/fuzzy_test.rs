@@ -0,0 +1,70 @@
+mod common;
+
+use common::*;
+use film_parser::fuzzy::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_matcher() -> FuzzyMatcher {
+        FuzzyMatcher::new(&[
+            film("Midsommar")
+                .director("Ari Aster")
+                .stars(&["Florence Pugh", "Jack Reynor"])
+                .build(),
+            film("Hereditary")
+                .director("Ari Aster")
+                .stars(&["Toni Collette", "Florence Pugh"])
+                .build(),
+            film("Little Women")
+                .director("Greta Gerwig")
+                .stars(&["Saoirse Ronan", "Florence Pugh"])
+                .build(),
+        ])
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("Midsomar", "Midsommar"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("Ёлка", "Ёлки"), 1);
+    }
+
+    #[test]
+    fn test_lookup_ranks_candidates() {
+        let matcher = sample_matcher();
+        let matches = matcher.lookup("florence pug", None, 5);
+        assert_eq!(matches[0].text, "Florence Pugh");
+        assert_eq!(matches[0].kind, MatchKind::Person);
+        assert!(matches[0].score > 0.8);
+        assert!(matches.windows(2).all(|w| w[0].score >= w[1].score));
+        // People appearing in several films are listed once.
+        assert_eq!(
+            matches.iter().filter(|m| m.text == "Florence Pugh").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_exact_match_scores_one() {
+        let matches = sample_matcher().lookup("HEREDITARY", Some(MatchKind::Title), 1);
+        assert_eq!(matches[0].text, "Hereditary");
+        assert_eq!(matches[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_suggest() {
+        let matcher = sample_matcher();
+        assert_eq!(
+            matcher.suggest("Midsomar", MatchKind::Title),
+            Some("Midsommar".to_string())
+        );
+        assert_eq!(
+            matcher.suggest("greta gerwik", MatchKind::Person),
+            Some("Greta Gerwig".to_string())
+        );
+        assert_eq!(matcher.suggest("zzzzzz", MatchKind::Title), None);
+    }
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::*;
+use film_parser::graph::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> CollaborationGraph {
+        CollaborationGraph::build(&[
+            film("Hereditary")
+                .director("Ari Aster")
+                .stars(&["Toni Collette", "Alex Wolff"])
+                .build(),
+            film("Midsommar")
+                .director("Ari Aster")
+                .stars(&["Florence Pugh", "Will Poulter"])
+                .build(),
+            film("Beau Is Afraid")
+                .director("Ari Aster")
+                .stars(&["Joaquin Phoenix", "Alex Wolff"])
+                .build(),
+            film("Knives Out")
+                .director("Rian Johnson")
+                .stars(&["Toni Collette", "Daniel Craig"])
+                .build(),
+            film("Skyfall")
+                .director("Sam Mendes")
+                .writer("Neal Purvis")
+                .stars(&["Daniel Craig", "Judi Dench"])
+                .build(),
+            film("Isolated")
+                .director("Nobody Known")
+                .stars(&["Lonely Star"])
+                .build(),
+        ])
+    }
+
+    #[test]
+    fn test_people_are_shared_between_roles() {
+        let graph = sample_graph();
+        let persons = graph
+            .nodes()
+            .iter()
+            .filter(|node| node.kind == NodeKind::Person)
+            .count();
+        assert_eq!(persons, 13);
+        assert!(graph.person("ari aster").is_ok());
+        assert!(graph.person("Jane Doe").is_err());
+    }
+
+    #[test]
+    fn test_repeated_credits_are_counted_once() {
+        let graph = CollaborationGraph::build(&[film("Us")
+            .director("Jordan Peele")
+            .stars(&["Lupita Nyong'o", "Lupita Nyong'o", "Winston Duke"])
+            .build()]);
+        let peele = graph.person("Jordan Peele").unwrap();
+
+        assert_eq!(graph.credits().len(), 4);
+        assert_eq!(graph.credits_of(peele).count(), 2);
+    }
+
+    #[test]
+    fn test_collaborators_of_director() {
+        let graph = sample_graph();
+        let stars = graph
+            .collaborators("Ari Aster", Some(Role::Star))
+            .expect("Failed to find collaborators");
+        assert_eq!(stars[0].name, "Alex Wolff");
+        assert_eq!(
+            stars[0].films,
+            vec!["Hereditary (2020)", "Beau Is Afraid (2020)"]
+        );
+        assert_eq!(stars.len(), 5);
+
+        let writers = graph
+            .collaborators("Toni Collette", Some(Role::Writer))
+            .expect("Failed to find collaborators");
+        let names: Vec<&str> = writers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Ari Aster", "Rian Johnson"]);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let graph = sample_graph();
+        let path = graph
+            .shortest_path("Florence Pugh", "Judi Dench")
+            .expect("Failed to find people")
+            .expect("People should be connected");
+        let names: Vec<&str> = path.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names.first(), Some(&"Florence Pugh"));
+        assert_eq!(names.last(), Some(&"Judi Dench"));
+        assert_eq!(path.len(), 9);
+    }
+
+    #[test]
+    fn test_unconnected_people() {
+        let graph = sample_graph();
+        let path = graph
+            .shortest_path("Lonely Star", "Ari Aster")
+            .expect("Failed to find people");
+        assert!(path.is_none());
+    }
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::*;
+use film_parser::index::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn sample_index() -> SearchIndex {
+        SearchIndex::build(&[
+            film("Midsommar")
+                .stars(&["Florence Pugh"])
+                .description("Friends travel to a festival in Sweden.")
+                .build(),
+            film("Hereditary")
+                .stars(&["Toni Collette"])
+                .description("A family is haunted after the death of their grandmother.")
+                .build(),
+            film("The Haunting")
+                .stars(&["Florence Small"])
+                .description("Strangers stay in a haunted house in a small town.")
+                .build(),
+            film("Ёлки")
+                .stars(&["Иван Ургант"])
+                .description("Новогодняя комедия.")
+                .build(),
+        ])
+    }
+
+    fn titles(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse_query("Haunt* \"small town\" pugh"),
+            vec![
+                QueryClause::Prefix("haunt".to_string()),
+                QueryClause::Phrase(vec!["small".to_string(), "town".to_string()]),
+                QueryClause::Term("pugh".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_terms_and_case_folding() {
+        let index = sample_index();
+        assert_eq!(titles(&index.search("PUGH", 10)), vec!["Midsommar"]);
+        assert_eq!(titles(&index.search("ёлки", 10)), vec!["Ёлки"]);
+        assert_eq!(
+            titles(&index.search("florence haunted", 10)),
+            vec!["The Haunting"]
+        );
+        assert!(index.search("zombie", 10).is_empty());
+    }
+
+    #[test]
+    fn test_terms_are_unicode_case_folded() {
+        assert_eq!(tokenize("Straße, ΣΊΣΥΦΟΣ"), vec!["strasse", "σίσυφοσ"]);
+
+        let index = SearchIndex::build(&[film("Berlin")
+            .description("A walk down the Straße.")
+            .build()]);
+        assert_eq!(titles(&index.search("STRASSE", 10)), vec!["Berlin"]);
+        assert_eq!(titles(&index.search("straße", 10)), vec!["Berlin"]);
+    }
+
+    #[test]
+    fn test_prefix_and_phrase() {
+        let index = sample_index();
+        assert_eq!(index.search("haunt*", 10).len(), 2);
+        assert_eq!(
+            titles(&index.search("\"small town\"", 10)),
+            vec!["The Haunting"]
+        );
+        assert!(index.search("\"town small\"", 10).is_empty());
+        // Phrases do not span two names.
+        assert!(index.search("\"pugh friends\"", 10).is_empty());
+    }
+
+    #[test]
+    fn test_index_is_rebuilt_when_source_changes() {
+        let dir = std::env::temp_dir().join(format!("film_parser_index_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("films.txt");
+        let line = "Title: Raw; Year: 2016; Director: Julia Ducournau; Writer: Julia Ducournau; \
+                    Genre: [Drama, Horror]; Stars: [Garance Marillier]; Description: A vet student tries meat.";
+        fs::write(&source, line).unwrap();
+        let source = source.to_str().unwrap();
+
+        let strict = ParseOptions::strict();
+        let opened = SearchIndex::open_or_build(source, &strict).expect("Failed to build index");
+        assert!(opened.rebuilt);
+        assert!(opened.save_error.is_none());
+        assert_eq!(opened.index.len(), 1);
+        let opened = SearchIndex::open_or_build(source, &strict).expect("Failed to load index");
+        assert!(!opened.rebuilt);
+        let index_path = SearchIndex::index_path(Path::new(source));
+        let saved = fs::read_to_string(&index_path).unwrap();
+        assert!(saved.contains("\"format\":1,"));
+        fs::write(&index_path, saved.replace("\"format\":1,", "")).unwrap();
+        let opened = SearchIndex::open_or_build(source, &strict).expect("Failed to rebuild index");
+        assert!(opened.rebuilt);
+        let opened = SearchIndex::open_or_build(source, &ParseOptions::lenient())
+            .expect("Failed to rebuild index");
+        assert!(opened.rebuilt);
+
+        fs::write(
+            source,
+            format!("{}\n{}", line, line.replace("Raw", "Titane")),
+        )
+        .unwrap();
+        let opened = SearchIndex::open_or_build(source, &strict).expect("Failed to rebuild index");
+        assert!(opened.rebuilt);
+        assert_eq!(titles(&opened.index.search("titane", 10)), vec!["Titane"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_index_that_cannot_be_saved_is_kept_in_memory() {
+        let dir = std::env::temp_dir().join(format!("film_parser_unsaved_{}", std::process::id()));
+        let source = dir.join("films.txt");
+        fs::create_dir_all(SearchIndex::index_path(&source)).unwrap();
+        fs::write(
+            &source,
+            "Title: Raw; Year: 2016; Director: Julia Ducournau; Writer: Julia Ducournau; \
+             Genre: [Drama, Horror]; Stars: [Garance Marillier]; Description: A vet student.",
+        )
+        .unwrap();
+
+        let opened = SearchIndex::open_or_build(source.to_str().unwrap(), &ParseOptions::strict())
+            .expect("Failed to build index");
+        assert!(opened.rebuilt);
+        assert!(opened.save_error.is_some());
+        assert_eq!(titles(&opened.index.search("raw", 10)), vec!["Raw"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+use film_parser::keys::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UKRAINIAN: &str = "Назва: Raw; Рік: 2016; Режисер: Julia Ducournau; Сценарист: Julia Ducournau; Жанр: [Drama, Horror]; Актори: [Garance Marillier, Ella Rumpf]; Опис: A vet student.";
+
+    fn parse_with_keys(input: &str) -> Result<Vec<Film>, FilmParserError> {
+        parse_film_lines_with(
+            vec![input.to_string()],
+            &ParseOptions::strict().with_key_table(KeyTable::builtin()),
+        )
+    }
+
+    #[test]
+    fn test_ukrainian_keys_are_parsed() {
+        let films = parse_with_keys(UKRAINIAN).expect("Failed to parse Ukrainian keys");
+
+        assert_eq!(films[0].title, "Raw");
+        assert_eq!(films[0].year, 2016);
+        assert_eq!(films[0].stars, vec!["Garance Marillier", "Ella Rumpf"]);
+    }
+
+    #[test]
+    fn test_keys_match_in_any_case_and_language() {
+        let input = "title: Raw; AÑO: 2016; Director: Julia Ducournau; guionista: Julia Ducournau; Жанр: [Drama]; STARS: [Ella Rumpf]; Descripción: A vet student.";
+        let films = parse_with_keys(input).expect("Failed to parse mixed keys");
+
+        assert_eq!(films[0].writer, "Julia Ducournau");
+        assert_eq!(films[0].description, "A vet student.");
+    }
+
+    #[test]
+    fn test_localised_keys_need_a_table() {
+        let result = parse_film_lines(vec![UKRAINIAN.to_string()]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_values_with_colons_are_kept() {
+        let table = KeyTable::builtin();
+        assert_eq!(
+            table.normalise("назва : Alien: Romulus;рік: 2024"),
+            "Title : Alien: Romulus;Year: 2024"
+        );
+    }
+
+    #[test]
+    fn test_description_keeps_key_like_words() {
+        let input = UKRAINIAN.replace(
+            "Опис: A vet student.",
+            "Опис: Two parts; genre: unclear; опис: second.",
+        );
+        let films = parse_with_keys(&input).expect("Failed to parse the description");
+
+        assert_eq!(films[0].genre, vec!["Drama", "Horror"]);
+        assert_eq!(
+            films[0].description,
+            "Two parts; genre: unclear; опис: second."
+        );
+        assert_eq!(
+            KeyTable::builtin().normalise("Опис: a; жанр: b"),
+            "Description: a; жанр: b"
+        );
+    }
+
+    #[test]
+    fn test_records_are_written_with_localised_keys() {
+        let film = parse_with_keys(UKRAINIAN).unwrap().remove(0);
+        let table = KeyTable::builtin();
+
+        assert_eq!(
+            film.to_record_line_in(table.language("uk").unwrap()),
+            UKRAINIAN
+        );
+        assert!(film.to_record_line().starts_with("Title: Raw; Year: 2016;"));
+    }
+
+    #[test]
+    fn test_table_file_adds_languages() {
+        let table = KeyTable::parse(
+            "# Polish partners\npl = Tytuł, Rok, Reżyser, Scenarzysta, Gatunek, Obsada, Opis\n",
+        )
+        .unwrap();
+
+        assert_eq!(table.field("tytuł"), Some(FilmField::Title));
+        assert_eq!(
+            table.language("pl").unwrap().key(FilmField::Stars),
+            "Obsada"
+        );
+        assert!(matches!(
+            KeyTable::parse("pl = Tytuł, Rok"),
+            Err(FilmParserError::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use common::*;
+use film_parser::lint::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(issues: &[LintIssue]) -> Vec<LintRule> {
+        issues.iter().map(|issue| issue.rule).collect()
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let films = vec![film("Midsommar")
+            .year(2030)
+            .stars(&["Florence Pugh", "florence pugh"])
+            .description("A festival in Sweden")
+            .director("Ari Aster")
+            .genre(&["Horror", ""])
+            .build()];
+        let issues = Linter::new(LintConfig::default())
+            .with_current_year(2026)
+            .lint(&films);
+
+        assert_eq!(
+            rules(&issues),
+            vec![
+                LintRule::FutureYear,
+                LintRule::EmptyListItem,
+                LintRule::DuplicateStar,
+                LintRule::DescriptionPunctuation,
+            ]
+        );
+        assert_eq!(issues[1].message, "genre item 2 is empty");
+        assert_eq!(issues[1].severity, Severity::Error);
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_clean_film_has_no_issues() {
+        let mut clean = film("Midsommar")
+            .year(2019)
+            .stars(&["Florence Pugh"])
+            .description("A festival.")
+            .director("Ari Aster")
+            .genre(&["Horror", ""])
+            .build();
+        clean.genre.pop();
+        let issues = Linter::new(LintConfig::default())
+            .with_current_year(2026)
+            .lint(&[clean]);
+
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_config_changes_severities() {
+        let config = LintConfig::parse(
+            "# curated data\nfuture-year = error\n\ndescription-punctuation = off\n",
+        )
+        .unwrap();
+        let films = vec![film("Midsommar")
+            .year(2030)
+            .stars(&["Florence Pugh"])
+            .description("A festival")
+            .director("Ari Aster")
+            .genre(&["Horror", ""])
+            .build()];
+        let issues = Linter::new(config).with_current_year(2026).lint(&films);
+
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::FutureYear, LintRule::EmptyListItem]
+        );
+        assert_eq!(issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_long_title_and_unnamed_director() {
+        let mut odd = film(&"A".repeat(MAX_TITLE_LENGTH + 1))
+            .year(2019)
+            .stars(&["X"])
+            .description("Fine.")
+            .director("Ari Aster")
+            .genre(&["Horror", ""])
+            .build();
+        odd.genre.pop();
+        odd.director = "Unknown".to_string();
+        let issues = Linter::new(LintConfig::default())
+            .with_current_year(2026)
+            .lint(&[odd]);
+
+        assert_eq!(
+            rules(&issues),
+            vec![LintRule::LongTitle, LintRule::UnnamedDirector]
+        );
+    }
+
+    #[test]
+    fn test_director_must_be_credited() {
+        let mut film = film("Hereditary")
+            .year(2018)
+            .stars(&["Toni Collette"])
+            .description("A family.")
+            .director("Ari Aster")
+            .genre(&["Horror", ""])
+            .build();
+        film.genre.pop();
+        film.writer = "Someone Else".to_string();
+        let linter = Linter::new(LintConfig::default()).with_current_year(2026);
+
+        let issues = linter.lint(std::slice::from_ref(&film));
+        assert_eq!(rules(&issues), vec![LintRule::DirectorNotCredited]);
+        assert_eq!(
+            issues[0].message,
+            "director Ari Aster is not credited as the writer or a star"
+        );
+        assert_eq!(issues[0].severity, Severity::Warning);
+
+        film.stars.push("ari aster".to_string());
+        assert!(linter.lint(&[film]).is_empty());
+    }
+
+    #[test]
+    fn test_dropped_list_items_follow_the_config() {
+        let line = "Title: Raw; Year: 2016; Director: Julia Ducournau; Writer: Julia Ducournau; Genre: [Drama, ]; Stars: [Ella Rumpf]; Description: A vet student.";
+        let lines = vec![sources::SourceLine {
+            text: line.to_string(),
+            location: SourceLocation {
+                path: "films.txt".into(),
+                line: 4,
+                byte_span: 0..line.len(),
+            },
+        }];
+        let (films, warnings) =
+            sources::parse_source_lines(lines, &ParseOptions::lenient()).unwrap();
+
+        let issues = Linter::new(LintConfig::default())
+            .with_current_year(2026)
+            .lint_with_warnings(&films, &warnings);
+        assert_eq!(rules(&issues), vec![LintRule::EmptyListItem]);
+        assert_eq!(issues[0].message, "genre item 2 is empty");
+        assert_eq!(issues[0].source.as_ref().unwrap().line, 4);
+
+        let config = LintConfig::parse("empty-list-item = off").unwrap();
+        let issues = Linter::new(config)
+            .with_current_year(2026)
+            .lint_with_warnings(&films, &warnings);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_config_names_the_line() {
+        match LintConfig::parse("future-year = error\nno-such-rule = warn\n") {
+            Err(FilmParserError::ConfigError(message)) => assert!(message.starts_with("line 2")),
+            other => panic!("expected a config error, got {:?}", other),
+        }
+        assert!(LintConfig::parse("future-year = loud").is_err());
+    }
+
+    #[test]
+    fn test_current_year_is_plausible() {
+        assert!(current_year() >= 2024);
+    }
+}
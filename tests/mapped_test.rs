@@ -0,0 +1,82 @@
+mod common;
+
+use common::*;
+use film_parser::mapped::*;
+use film_parser::sources::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const RECORD: &str = "Title: Raw; Year: 2016; Director: Julia Ducournau; Writer: Julia Ducournau; Genre: [Drama, Horror]; Stars: [Garance Marillier, Ella Rumpf]; Description: A vet student.";
+
+    #[test]
+    fn test_line_index() {
+        let text = "first\r\nsecond\n\nlast\n";
+        let index = LineIndex::new(text);
+
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.line(text, 0), "first");
+        assert_eq!(index.span(text, 1), 7..13);
+        assert_eq!(index.line(text, 2), "");
+        assert_eq!(index.line(text, 3), "last");
+        assert!(LineIndex::new("").is_empty());
+        assert_eq!(LineIndex::new("only").line("only", 0), "only");
+    }
+
+    #[test]
+    fn test_utf8_files_are_mapped() {
+        let path = temp_file("bom.txt", format!("\u{feff}{}\n", RECORD).as_bytes());
+        let text = MappedText::open(&path, None).unwrap();
+
+        assert!(text.is_mapped());
+        assert_eq!(text.as_str(), format!("{}\n", RECORD));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_other_files_are_decoded() {
+        let path = temp_file("cp1251.txt", [0xCF, 0xF0, 0xE8, 0xE2, 0xB3, 0xF2]);
+        let text = MappedText::open(&path, Some(encoding_rs::WINDOWS_1251)).unwrap();
+        assert!(!text.is_mapped());
+        assert_eq!(text.as_str(), "Привіт");
+
+        let error = MappedText::open(&path, None).err().unwrap();
+        assert!(error.to_string().contains("--encoding"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_empty_file_has_no_lines() {
+        let path = temp_file("empty.txt", b"");
+        let (films, warnings) = parse_file(&path, &ParseOptions::strict()).unwrap();
+
+        assert!(films.is_empty());
+        assert!(warnings.is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_file_matches_source_lines() {
+        let other = RECORD.replace("Raw", "Titane");
+        let path = temp_file(
+            "crlf.txt",
+            format!("{}\r\n\r\n{}", RECORD, other).as_bytes(),
+        );
+        let options = ParseOptions::strict();
+
+        let (mapped, _) = parse_file(&path, &options).unwrap();
+        let (read, _) = parse_source_lines(read_source_lines(&path).unwrap(), &options).unwrap();
+
+        assert_eq!(format!("{:?}", mapped), format!("{:?}", read));
+        let source = mapped[1].source.as_ref().unwrap();
+        assert_eq!(source.line, 3);
+        assert_eq!(
+            source.byte_span,
+            RECORD.len() + 4..RECORD.len() + 4 + other.len()
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
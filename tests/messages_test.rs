@@ -0,0 +1,137 @@
+use film_parser::messages::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn location() -> SourceLocation {
+        SourceLocation {
+            path: PathBuf::from("films.txt"),
+            line: 3,
+            byte_span: 10..20,
+        }
+    }
+
+    #[test]
+    fn test_english_errors_match_display() {
+        let errors = vec![
+            FilmParserError::NoFileFound("a.txt".to_string()),
+            FilmParserError::FileReadingError("a.txt: denied".to_string()),
+            FilmParserError::FileOpeningError("a.txt".to_string()),
+            FilmParserError::FileCreatingError("a.txt".to_string()),
+            FilmParserError::FileWritingError("a.txt".to_string()),
+            FilmParserError::ParsingError("Title: Raw".to_string()),
+            FilmParserError::RuleParsingError("year".to_string(), "Year".to_string()),
+            FilmParserError::EmptyListItem("stars".to_string(), 2),
+            FilmParserError::MissingFieldsError,
+            FilmParserError::UnknownRule("film".to_string()),
+            FilmParserError::UnknownField("budget".to_string()),
+            FilmParserError::InvalidOption("top x".to_string()),
+            FilmParserError::FilmNotFound("Rwa".to_string()),
+            FilmParserError::PersonNotFound("Ari".to_string()),
+            FilmParserError::SerializationError("broken".to_string()),
+            FilmParserError::AtSource(location(), Box::new(FilmParserError::MissingFieldsError)),
+            FilmParserError::StrictModeError(WarningKind::UnknownGenre("Gore".to_string())),
+            FilmParserError::ConfigError("line 1".to_string()),
+            FilmParserError::QueryError("year >".to_string()),
+            FilmParserError::RecordError(
+                "Title: Raw".to_string(),
+                Box::new(FilmParserError::StrictModeError(WarningKind::KeyCase(
+                    "title".to_string(),
+                ))),
+            ),
+        ];
+
+        for error in errors {
+            assert_eq!(Language::English.error(&error), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_messages_are_translated_with_arguments() {
+        assert_eq!(Language::Ukrainian.translate("Error"), "Помилка");
+        assert_eq!(
+            Language::Ukrainian.format("Found {} of {} films.", &[&3, &10]),
+            "Знайдено 3 з 10 фільмів."
+        );
+        assert_eq!(
+            Language::English.format("Found {} of {} films.", &[&3, &10]),
+            "Found 3 of 10 films."
+        );
+    }
+
+    #[test]
+    fn test_untranslated_messages_stay_english() {
+        assert_eq!(
+            Language::Ukrainian.format("{} is new", &[&"Raw"]),
+            "Raw is new"
+        );
+    }
+
+    #[test]
+    fn test_nested_errors_are_translated() {
+        let error = FilmParserError::AtSource(
+            location(),
+            Box::new(FilmParserError::EmptyListItem("stars".to_string(), 2)),
+        );
+
+        assert_eq!(
+            Language::Ukrainian.error(&error),
+            "films.txt:3: Елемент 2 списку акторів порожній"
+        );
+    }
+
+    #[test]
+    fn test_query_errors_are_translated() {
+        let error = Query::parse("year ~ 2019").unwrap_err();
+        let english = Language::English.error(&error);
+        let ukrainian = Language::Ukrainian.error(&error);
+
+        assert_eq!(english, error.to_string());
+        assert!(english.contains("= operator `~` cannot be used with `year`"));
+        assert!(ukrainian.starts_with("Некоректний запит:\n"));
+        assert!(ukrainian.contains("year ~ 2019"));
+        assert!(ukrainian.contains("= оператор `~` не можна використовувати з `year`"));
+
+        let error = Query::parse("rating = 5").unwrap_err();
+        assert!(Language::Ukrainian
+            .error(&error)
+            .contains("= невідоме поле `rating`, очікується одне з:"));
+    }
+
+    #[test]
+    fn test_languages_are_read_from_codes_and_locales() {
+        assert_eq!(
+            Language::from_locale("uk_UA.UTF-8"),
+            Some(Language::Ukrainian)
+        );
+        assert_eq!(Language::from_locale("en_GB"), Some(Language::English));
+        assert_eq!(Language::from_locale("C"), None);
+        assert_eq!("uk".parse::<Language>().unwrap(), Language::Ukrainian);
+        assert!(matches!(
+            "xx".parse::<Language>(),
+            Err(FilmParserError::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn test_warnings_are_translated() {
+        let warning = ParseWarning {
+            kind: WarningKind::EmptyListItem("genre".to_string(), 1),
+            location: Some(location()),
+        };
+        let error = FilmParserError::StrictModeError(WarningKind::MissingField(FilmField::Writer));
+
+        assert_eq!(Language::English.warning(&warning), warning.to_string());
+        assert_eq!(
+            Language::Ukrainian.warning(&warning),
+            "films.txt:3: вилучено порожній елемент 1 списку жанрів"
+        );
+        assert_eq!(
+            Language::Ukrainian.error(&error),
+            "Відхилено в строгому режимі: бракує поля Writer"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use film_parser::options::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANONICAL: &str = "Title: Raw; Year: 2016; Director: Julia Ducournau; Writer: Julia Ducournau; Genre: [Drama, Horror]; Stars: [Garance Marillier, Ella Rumpf]; Description: A vet student.";
+
+    fn parse(
+        input: &str,
+        options: &ParseOptions,
+    ) -> Result<(Vec<Film>, Vec<ParseWarning>), FilmParserError> {
+        parse_film_lines_with_warnings(vec![input.to_string()], options)
+    }
+
+    fn warning_kinds(input: &str) -> Vec<WarningKind> {
+        let (_, warnings) =
+            parse(input, &ParseOptions::lenient()).expect("Failed to parse in lenient mode");
+        warnings.into_iter().map(|warning| warning.kind).collect()
+    }
+
+    #[test]
+    fn test_canonical_record_has_no_warnings() {
+        let (films, warnings) = parse(CANONICAL, &ParseOptions::strict()).unwrap();
+
+        assert_eq!(films[0].title, "Raw");
+        assert!(warnings.is_empty());
+        assert!(warning_kinds(CANONICAL).is_empty());
+    }
+
+    #[test]
+    fn test_key_case_is_rejected_in_strict_mode() {
+        let input = CANONICAL.replace("Director:", "director:");
+
+        assert!(parse(&input, &ParseOptions::strict()).is_err());
+        assert_eq!(
+            warning_kinds(&input),
+            vec![WarningKind::KeyCase("director".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_whitespace_around_keys_is_a_warning() {
+        let input = CANONICAL
+            .replace("Year: 2016;", "Year: 2016 ;")
+            .replace("Title: Raw", "Title :Raw");
+
+        assert!(parse(&input, &ParseOptions::strict()).is_err());
+        assert_eq!(
+            warning_kinds(&input),
+            vec![WarningKind::KeyWhitespace(FilmField::Title)]
+        );
+    }
+
+    #[test]
+    fn test_whitespace_before_separator_is_not_part_of_the_value() {
+        let input = CANONICAL
+            .replace("Title: Raw;", "Title: Raw ;")
+            .replace("Director: Julia Ducournau;", "Director: Julia Ducournau\t;");
+
+        let (films, warnings) = parse(&input, &ParseOptions::strict()).unwrap();
+        assert_eq!(films[0].title, "Raw");
+        assert_eq!(films[0].director, "Julia Ducournau");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_missing_fields_and_trailing_text_are_warnings() {
+        let input =
+            "Title: Raw; Year: 2016; Genre: [Drama]; Stars: [Garance Marillier]; she eats meat.";
+        let (films, warnings) = parse(input, &ParseOptions::lenient()).unwrap();
+
+        assert_eq!(films[0].stars, vec!["Garance Marillier"]);
+        let kinds: Vec<WarningKind> = warnings.into_iter().map(|warning| warning.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                WarningKind::TrailingText("she eats meat.".to_string()),
+                WarningKind::MissingField(FilmField::Director),
+                WarningKind::MissingField(FilmField::Writer),
+                WarningKind::MissingField(FilmField::Description),
+            ]
+        );
+        assert!(parse(input, &ParseOptions::strict()).is_err());
+    }
+
+    #[test]
+    fn test_description_may_contain_semicolons() {
+        let input = CANONICAL.replace("A vet student.", "A vet student; she eats meat.;");
+        let (films, warnings) = parse(&input, &ParseOptions::strict()).unwrap();
+
+        assert_eq!(films[0].description, "A vet student; she eats meat.");
+        assert!(warnings.is_empty());
+        let sample = read_lines("data/film_info.txt").unwrap();
+        let mandy = sample
+            .iter()
+            .find(|line| line.starts_with("Title: Mandy"))
+            .unwrap();
+        let films = parse_film_lines(vec![mandy.clone()]).unwrap();
+        assert!(films[0].description.contains("existence; but when"));
+    }
+
+    #[test]
+    fn test_title_and_year_are_required_in_lenient_mode() {
+        let result = parse(
+            "Title: Raw; Director: Julia Ducournau",
+            &ParseOptions::lenient(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_unknown_genres_are_only_checked_when_requested() {
+        let input = CANONICAL.replace("Horror", "Cannibal Drama");
+
+        assert!(parse(&input, &ParseOptions::strict()).is_ok());
+        assert!(parse(
+            &input,
+            &ParseOptions::strict().with_known_genres(&KNOWN_GENRES)
+        )
+        .is_err());
+        let (_, warnings) = parse(
+            &input,
+            &ParseOptions::lenient().with_known_genres(&KNOWN_GENRES),
+        )
+        .unwrap();
+        assert_eq!(
+            warnings[0].kind,
+            WarningKind::UnknownGenre("Cannibal Drama".to_string())
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use film_parser::parallel::*;
+use film_parser::sources::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn record(index: usize) -> String {
+        format!(
+            "Title: Film {}; Year: {}; Director: Director {}; Writer: Writer; Genre: [Drama]; Stars: [Star]; Description: Number {}.",
+            index,
+            1950 + index % 70,
+            index % 13,
+            index
+        )
+    }
+
+    fn records(count: usize) -> Vec<String> {
+        (0..count).map(record).collect()
+    }
+
+    fn source_lines(texts: Vec<String>) -> Vec<SourceLine> {
+        texts
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| SourceLine {
+                text,
+                location: SourceLocation {
+                    path: PathBuf::from("big.txt"),
+                    line: index + 1,
+                    byte_span: 0..0,
+                },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_films_keep_record_order() {
+        let count = CHUNK_SIZE * 3 + 7;
+        let options = ParseOptions::strict().with_jobs(4);
+        let films = parse_film_lines_with(records(count), &options).unwrap();
+
+        assert_eq!(films.len(), count);
+        for (index, film) in films.iter().enumerate() {
+            assert_eq!(film.title, format!("Film {}", index));
+        }
+    }
+
+    #[test]
+    fn test_parallel_and_sequential_results_are_equal() {
+        let lines = records(CHUNK_SIZE * 2 + 1);
+        let sequential =
+            parse_film_lines_with(lines.clone(), &ParseOptions::strict().with_jobs(1)).unwrap();
+        let parallel = parse_film_lines_with(lines, &ParseOptions::strict().with_jobs(0)).unwrap();
+
+        assert_eq!(format!("{:?}", sequential), format!("{:?}", parallel));
+    }
+
+    #[test]
+    fn test_first_broken_line_is_reported() {
+        let mut lines = records(CHUNK_SIZE * 3);
+        lines[CHUNK_SIZE * 2 + 5] = "Title: Broken; Year: soon".to_string();
+        lines[CHUNK_SIZE + 10] = "Title: Broken; Year: later".to_string();
+
+        let error = parse_source_lines(source_lines(lines), &ParseOptions::strict().with_jobs(3))
+            .unwrap_err();
+        match error {
+            FilmParserError::AtSource(location, _) => {
+                assert_eq!(location.line, CHUNK_SIZE + 11);
+            }
+            other => panic!("Expected an error at a source, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_warnings_keep_their_lines() {
+        let mut lines = records(CHUNK_SIZE * 2);
+        lines[3] = lines[3].replace("Title:", "title:");
+        lines[CHUNK_SIZE + 3] = lines[CHUNK_SIZE + 3].replace("Title:", "title:");
+        lines.insert(CHUNK_SIZE, String::new());
+
+        let (films, warnings) =
+            parse_source_lines(source_lines(lines), &ParseOptions::lenient().with_jobs(2)).unwrap();
+        let warned: Vec<usize> = warnings
+            .iter()
+            .map(|warning| warning.location.as_ref().unwrap().line)
+            .collect();
+
+        assert_eq!(films.len(), CHUNK_SIZE * 2);
+        assert_eq!(
+            films[CHUNK_SIZE].source.as_ref().unwrap().line,
+            CHUNK_SIZE + 2
+        );
+        assert_eq!(warned, vec![4, CHUNK_SIZE + 5]);
+    }
+
+    #[test]
+    fn test_map_records_returns_first_error() {
+        let numbers: Vec<usize> = (0..CHUNK_SIZE * 4).collect();
+        let result = map_records(numbers, 4, |number| {
+            if number % 700 == 699 {
+                Err(FilmParserError::ParsingError(number.to_string()))
+            } else {
+                Ok(number * 2)
+            }
+        });
+
+        match result {
+            Err(FilmParserError::ParsingError(number)) => assert_eq!(number, "699"),
+            other => panic!("Expected the first error, got {:?}", other),
+        }
+        let doubled = map_records((0..10).collect(), 0, |number: usize| Ok(number * 2)).unwrap();
+        assert_eq!(doubled, (0..20).step_by(2).collect::<Vec<_>>());
+    }
+}
@@ -1,4 +1,5 @@
 use film_parser::*;
+use pest::Parser;
 
 #[cfg(test)]
 mod tests {
@@ -180,4 +181,67 @@ mod tests {
         let result = parse_single_film(input);
         assert!(result.is_err());
     }
+
+    fn film_with_lists(genre: &str, stars: &str) -> String {
+        format!(
+            "Title: Some_Title; Year: 2024; Director: Some_Director; Writer: Some_Writer; \
+             Genre: [{}]; Stars: [{}]; Description: Some_Description.",
+            genre, stars
+        )
+    }
+
+    #[test]
+    fn test_empty_list_item_is_rejected() {
+        let input = film_with_lists("Drama", "Some_Actor_A,,Some_Actor_B");
+        let result = parse_film_lines(vec![input]);
+
+        match result {
+            Err(FilmParserError::RecordError(_, error)) => assert!(matches!(
+                *error,
+                FilmParserError::EmptyListItem(list, 2) if list == "stars"
+            )),
+            other => panic!("expected a parsing error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_empty_list_is_rejected() {
+        let result = parse_film_lines(vec![film_with_lists("", "Some_Actor_A")]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_blank_list_item_is_rejected() {
+        let input = film_with_lists("Drama,  , Mystery", "Some_Actor_A");
+        let pair = FilmParser::parse(Rule::file, &input)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        assert!(matches!(
+            Film::parse_to_struct(pair),
+            Err(FilmParserError::EmptyListItem(list, 2)) if list == "genre"
+        ));
+    }
+
+    #[test]
+    fn test_lenient_mode_drops_empty_list_items() {
+        let input = film_with_lists(" Drama , , Mystery", "Some_Actor_A,");
+        let films = parse_film_lines_with(vec![input], &ParseOptions::lenient())
+            .expect("Failed to parse in lenient mode");
+
+        assert_eq!(films[0].genre, vec!["Drama", "Mystery"]);
+        assert_eq!(films[0].stars, vec!["Some_Actor_A"]);
+    }
+
+    #[test]
+    fn test_padded_list_items_are_trimmed_in_strict_mode() {
+        let input = film_with_lists(" Drama ,Horror ", "Some_Actor_A ");
+        let (films, warnings) =
+            parse_film_lines_with_warnings(vec![input], &ParseOptions::strict()).unwrap();
+
+        assert_eq!(films[0].genre, vec!["Drama", "Horror"]);
+        assert_eq!(films[0].stars, vec!["Some_Actor_A"]);
+        assert!(warnings.is_empty());
+    }
 }
@@ -0,0 +1,93 @@
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_film() -> Film {
+        Film::new(
+            "Midsommar".to_string(),
+            2019,
+            "Ari Aster".to_string(),
+            "Ari Aster".to_string(),
+            vec![
+                "Mystery".to_string(),
+                "Drama".to_string(),
+                "Horror".to_string(),
+            ],
+            vec!["Florence Pugh".to_string(), "Jack Reynor".to_string()],
+            "Several friends travel to Sweden.".to_string(),
+        )
+    }
+
+    fn matches(query: &str) -> bool {
+        Query::parse(query)
+            .expect("Failed to parse valid query")
+            .matches(&sample_film())
+    }
+
+    #[test]
+    fn test_combined_query() {
+        assert!(matches(
+            r#"year >= 2015 and genre = Horror and star ~ "Pugh""#
+        ));
+        assert!(!matches(r#"year >= 2020 and genre = Horror"#));
+    }
+
+    #[test]
+    fn test_text_comparison_ignores_case() {
+        assert!(matches("director = \"ari aster\""));
+        assert!(matches("title ~ SOMMAR"));
+        assert!(matches("description ~ sweden"));
+    }
+
+    #[test]
+    fn test_list_fields() {
+        assert!(matches("genre = Drama"));
+        assert!(matches("genre != Comedy"));
+        assert!(!matches("genre != Drama"));
+        assert!(matches("stars ~ Reynor"));
+    }
+
+    #[test]
+    fn test_or_not_and_parentheses() {
+        assert!(matches("genre = Comedy or year = 2019"));
+        assert!(matches("not (genre = Comedy or year < 2000)"));
+        assert!(!matches("NOT year = 2019"));
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let err = Query::parse("yr >= 2015").unwrap_err().to_string();
+        assert!(err.contains("unknown field `yr`"));
+        assert!(err.contains("1:1"));
+    }
+
+    #[test]
+    fn test_invalid_operator_for_field() {
+        let err = Query::parse("year >= 2015 and title > Raw")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1:24"));
+        assert!(Query::parse("year ~ 2015").is_err());
+        assert!(Query::parse("year = soon").is_err());
+    }
+
+    #[test]
+    fn test_year_out_of_range() {
+        let err = Query::parse("title = Raw or year = 99999999999")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("year `99999999999` is too large"));
+        assert!(err.contains("1:23"));
+        assert!(Query::parse("year = 4294967295").is_ok());
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        assert!(Query::parse("").is_err());
+        assert!(Query::parse("title =").is_err());
+        assert!(Query::parse("genre = Horror and").is_err());
+        assert!(Query::parse("(year = 2019").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::*;
+use film_parser::recommend::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_films() -> Vec<Film> {
+        vec![
+            film("Hereditary")
+                .year(2018)
+                .director("Ari Aster")
+                .genre(&["Horror", "Drama"])
+                .stars(&["Hereditary Star"])
+                .description("A grieving family is haunted by a cult.")
+                .build(),
+            film("Midsommar")
+                .year(2019)
+                .director("Ari Aster")
+                .genre(&["Horror", "Drama"])
+                .stars(&["Midsommar Star"])
+                .description("Friends visit a cult festival in Sweden.")
+                .build(),
+            film("The Witch")
+                .year(2015)
+                .director("Robert Eggers")
+                .genre(&["Horror"])
+                .stars(&["The Witch Star"])
+                .description("A family in New England is haunted by witchcraft.")
+                .build(),
+            film("Paddington")
+                .year(2014)
+                .director("Paul King")
+                .genre(&["Comedy"])
+                .stars(&["Paddington Star"])
+                .description("A bear moves to London.")
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("The Family, haunted by A cult!"),
+            vec!["family", "haunted", "cult"]
+        );
+    }
+
+    #[test]
+    fn test_similar_ranks_and_explains() {
+        let films = sample_films();
+        let recommendations = Recommender::new(&films)
+            .similar("hereditary", 10)
+            .expect("Failed to find film");
+        let titles: Vec<&str> = recommendations
+            .iter()
+            .map(|r| r.film.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Midsommar", "The Witch", "Paddington"]);
+
+        let best = &recommendations[0].explanation;
+        assert_eq!(best.shared_genres, vec!["Horror", "Drama"]);
+        assert_eq!(best.shared_crew, vec!["Ari Aster"]);
+        assert!(best.shared_cast.is_empty());
+        assert_eq!(best.year_difference, 1);
+        assert_eq!(best.genre.similarity, 1.0);
+        assert!(recommendations[1]
+            .explanation
+            .shared_terms
+            .contains(&"haunted".to_string()));
+
+        let total = best.genre.contribution
+            + best.crew.contribution
+            + best.cast.contribution
+            + best.year.contribution
+            + best.description.contribution;
+        assert!((recommendations[0].score - total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weights_change_ranking() {
+        let films = sample_films();
+        let weights = Weights {
+            genre: 0.0,
+            crew: 0.0,
+            cast: 0.0,
+            year: 0.0,
+            description: 1.0,
+        };
+        let recommendations = Recommender::with_weights(&films, weights)
+            .similar("Hereditary", 1)
+            .expect("Failed to find film");
+        assert_eq!(recommendations[0].film.title, "The Witch");
+    }
+
+    #[test]
+    fn test_shared_director_outranks_genre_only_match() {
+        let mut films = sample_films();
+        films[0].genre = vec!["Thriller".to_string(), "Horror".to_string()];
+        films[1].genre = vec!["Drama".to_string(), "Horror".to_string()];
+        films[1].stars = (0..20).map(|index| format!("Extra {}", index)).collect();
+        films[2].genre = vec!["Drama".to_string(), "Horror".to_string()];
+        films[2].year = 2019;
+        let recommender = Recommender::new(&films);
+
+        let recommendations = recommender.similar("Midsommar", 2).unwrap();
+        assert_eq!(recommendations[0].film.title, "Hereditary");
+        assert_eq!(recommendations[0].explanation.crew.similarity, 1.0);
+        assert_eq!(recommendations[1].film.title, "The Witch");
+        assert!(
+            recommendations[1].explanation.genre.similarity
+                > recommendations[0].explanation.genre.similarity
+        );
+    }
+
+    #[test]
+    fn test_unknown_title() {
+        let films = sample_films();
+        assert!(Recommender::new(&films).similar("Midsomar", 5).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use film_parser::dedupe::MergePolicy;
+use film_parser::sources::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn record(title: &str, stars: &str) -> String {
+        format!(
+            "Title: {}; Year: 2019; Director: Ari Aster; Writer: Ari Aster; Genre: [Horror]; Stars: [{}]; Description: A film.",
+            title, stars
+        )
+    }
+
+    /// Creates `a.txt`, `nested/b.txt` and `notes.md` in a fresh directory.
+    fn sample_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "film_parser_sources_{}_{}",
+            name,
+            std::process::id()
+        ));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(
+            dir.join("a.txt"),
+            format!(
+                "{}\n{}\n",
+                record("Midsommar", "Florence Pugh"),
+                record("Hereditary", "Toni Collette")
+            ),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("nested").join("b.txt"),
+            format!("{}\n", record("Midsommar", "Will Poulter")),
+        )
+        .unwrap();
+        fs::write(dir.join("notes.md"), "Not a catalogue").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_directory_is_walked_recursively() {
+        let dir = sample_dir("walk");
+        let files = expand_inputs(&[dir.display().to_string()]).unwrap();
+
+        assert_eq!(
+            files,
+            vec![dir.join("a.txt"), dir.join("nested").join("b.txt")]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_glob_pattern_and_repeated_file() {
+        let dir = sample_dir("glob");
+        let pattern = dir.join("*.txt").display().to_string();
+        let file = dir.join("a.txt").display().to_string();
+        let files = expand_inputs(&[pattern, file]).unwrap();
+
+        assert_eq!(files, vec![dir.join("a.txt")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_input() {
+        assert!(matches!(
+            expand_inputs(&["no/such/file.txt".to_string()]),
+            Err(FilmParserError::NoFileFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_films_keep_their_source() {
+        let dir = sample_dir("source");
+        let (films, warnings) = load_files(&[dir.join("a.txt")], &ParseOptions::default()).unwrap();
+
+        assert!(warnings.is_empty());
+
+        assert_eq!(
+            films[1].source,
+            Some(SourceLocation {
+                path: dir.join("a.txt"),
+                line: 2,
+                byte_span: 132..264,
+            })
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_duplicates_across_files_are_merged() {
+        let dir = sample_dir("merge");
+        let (catalogue, _) =
+            load_catalogue(&[dir.display().to_string()], None, &ParseOptions::default()).unwrap();
+
+        assert_eq!(catalogue.films.len(), 2);
+        assert_eq!(catalogue.merged.len(), 1);
+        assert_eq!(
+            catalogue.films[0].stars,
+            vec!["Florence Pugh", "Will Poulter"]
+        );
+        assert_eq!(catalogue.films[0].source.as_ref().unwrap().line, 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_single_file_is_only_merged_with_a_policy() {
+        let dir = sample_dir("single");
+        let file = dir.join("a.txt");
+        fs::write(
+            &file,
+            format!(
+                "{}\n{}\n",
+                record("Midsommar", "Florence Pugh"),
+                record("Midsommar", "Will Poulter")
+            ),
+        )
+        .unwrap();
+        let inputs = [file.display().to_string()];
+
+        let (catalogue, _) = load_catalogue(&inputs, None, &ParseOptions::default()).unwrap();
+        assert_eq!(catalogue.films.len(), 2);
+        assert!(catalogue.merged.is_empty());
+
+        let policy = Some(MergePolicy::default());
+        let (catalogue, _) = load_catalogue(&inputs, policy, &ParseOptions::default()).unwrap();
+        assert_eq!(catalogue.films.len(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_byte_spans_skip_line_breaks() {
+        let dir = sample_dir("spans");
+        let file = dir.join("crlf.txt");
+        fs::write(&file, "first\r\n\nthird").unwrap();
+        let lines = read_source_lines(&file).unwrap();
+
+        let spans: Vec<_> = lines.iter().map(|l| l.location.byte_span.clone()).collect();
+        assert_eq!(spans, vec![0..5, 7..7, 8..13]);
+        assert_eq!(lines[2].text, "third");
+        assert_eq!(lines[2].location.line, 3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_error_names_file_and_line() {
+        let dir = sample_dir("error");
+        let file = dir.join("broken.txt");
+        fs::write(
+            &file,
+            format!("{}\nTitle: Broken\n", record("Raw", "Ella Rumpf")),
+        )
+        .unwrap();
+
+        match parse_file(&file, &ParseOptions::default()) {
+            Err(FilmParserError::AtSource(location, _)) => {
+                assert_eq!(location.path, file);
+                assert_eq!(location.line, 2);
+            }
+            other => panic!("expected an error at line 2, got {:?}", other),
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_source_lines_give_films_their_source() {
+        let dir = sample_dir("lines");
+        let file = dir.join("a.txt");
+        let lines = read_source_lines(&file).unwrap();
+
+        let films = parse_film_lines(lines.clone()).unwrap();
+        let source = films[1].source.as_ref().unwrap();
+        assert_eq!(source.path, file);
+        assert_eq!(source.line, 2);
+        assert_eq!(source, &lines[1].location);
+
+        let mut broken = lines;
+        broken[1].text = "Title: Broken".to_string();
+        match parse_film_lines(broken) {
+            Err(FilmParserError::AtSource(location, _)) => assert_eq!(location.line, 2),
+            other => panic!("expected an error at line 2, got {:?}", other),
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
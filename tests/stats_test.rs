@@ -0,0 +1,114 @@
+mod common;
+
+use common::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_films() -> Vec<Film> {
+        vec![
+            film("Raw")
+                .year(2016)
+                .director("Julia Ducournau")
+                .genre(&["Horror", "Drama"])
+                .stars(&["Garance Marillier"])
+                .description(&"x".repeat(16))
+                .build(),
+            film("Hereditary")
+                .year(2018)
+                .director("Ari Aster")
+                .genre(&["Horror", "Drama"])
+                .stars(&["Toni Collette", "Alex Wolff"])
+                .description(&"x".repeat(18))
+                .build(),
+            film("Midsommar")
+                .year(2019)
+                .director("Ari Aster")
+                .genre(&["Horror", "Drama"])
+                .stars(&["Florence Pugh", "Jack Reynor"])
+                .description(&"x".repeat(19))
+                .build(),
+            film("A Quiet Place")
+                .year(2018)
+                .director("John Krasinski")
+                .writer("Bryan Woods")
+                .genre(&["Horror", "Drama"])
+                .stars(&["Emily Blunt", "Noah Jupe", "Florence Pugh"])
+                .description(&"x".repeat(18))
+                .build(),
+            film("Smile")
+                .year(2022)
+                .director("Parker Finn")
+                .genre(&["Horror", "Drama"])
+                .stars(&["Sosie Bacon"])
+                .description(&"x".repeat(22))
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn test_counts() {
+        let stats = CatalogueStats::compute(&sample_films());
+        assert_eq!(stats.film_count, 5);
+        assert_eq!(stats.genres[0].count, 5);
+        assert_eq!(
+            stats
+                .decades
+                .iter()
+                .map(|c| (c.key.as_str(), c.count))
+                .collect::<Vec<_>>(),
+            vec![("2010s", 4), ("2020s", 1)]
+        );
+        assert_eq!(stats.years[1].key, "2018");
+        assert_eq!(stats.years[1].count, 2);
+    }
+
+    #[test]
+    fn test_repeated_genres_and_years_before_1000() {
+        let silent = film("Häxan")
+            .year(999)
+            .director("Benjamin Christensen")
+            .genre(&["Horror", "Horror"])
+            .stars(&[])
+            .build();
+        let mut films = sample_films();
+        films.push(silent);
+        let stats = CatalogueStats::compute(&films);
+
+        assert_eq!(stats.genres[0].key, "Horror");
+        assert_eq!(stats.genres[0].count, 6);
+        assert_eq!(stats.years[0].key, "999");
+        assert_eq!(stats.decades[0].key, "990s");
+        assert_eq!(stats.decades[1].key, "2010s");
+    }
+
+    #[test]
+    fn test_top_lists() {
+        let stats = CatalogueStats::compute_with_top(&sample_films(), 1);
+        assert_eq!(stats.top_directors.len(), 1);
+        assert_eq!(stats.top_directors[0].key, "Ari Aster");
+        assert_eq!(stats.top_directors[0].count, 2);
+        assert_eq!(stats.top_stars[0].key, "Florence Pugh");
+    }
+
+    #[test]
+    fn test_averages_and_shares() {
+        let stats = CatalogueStats::compute(&sample_films());
+        assert!((stats.average_cast_size - 9.0 / 5.0).abs() < 1e-9);
+        assert!((stats.director_writer_share - 0.8).abs() < 1e-9);
+        assert_eq!(stats.description_lengths.min, 16);
+        assert_eq!(stats.description_lengths.max, 22);
+        assert_eq!(stats.description_lengths.median, 18.0);
+    }
+
+    #[test]
+    fn test_empty_catalogue_and_json() {
+        let stats = CatalogueStats::compute(&[]);
+        assert_eq!(stats.film_count, 0);
+        assert_eq!(stats.average_cast_size, 0.0);
+        let json = stats.to_json().expect("Failed to serialize stats");
+        assert!(json.contains("\"film_count\": 0"));
+    }
+}
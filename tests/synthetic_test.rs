@@ -0,0 +1,59 @@
+use film_parser::synthetic::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use film_parser::dedupe;
+
+    #[test]
+    fn test_same_settings_give_same_catalogue() {
+        let first = SyntheticCatalogue::new(50).record_lines();
+        let second = SyntheticCatalogue::new(50).record_lines();
+        let other = SyntheticCatalogue::new(50).with_seed(7).record_lines();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_records_parse_in_strict_mode() {
+        let catalogue = SyntheticCatalogue::new(200).with_cast(40);
+        let films = parse_film_lines_with(
+            catalogue.record_lines(),
+            &ParseOptions::strict().with_known_genres(&options::KNOWN_GENRES),
+        )
+        .unwrap();
+
+        assert_eq!(films.len(), 200);
+        assert!(films.iter().all(|film| film.stars.len() == 40));
+        assert_eq!(
+            format!("{:?}", films),
+            format!("{:?}", catalogue.generate())
+        );
+    }
+
+    #[test]
+    fn test_names_are_unicode() {
+        let films = SyntheticCatalogue::new(100).generate();
+
+        assert!(films
+            .iter()
+            .flat_map(|film| film.stars.iter())
+            .any(|star| !star.is_ascii()));
+        assert!(films.iter().any(|film| !film.title.is_ascii()));
+    }
+
+    #[test]
+    fn test_films_are_not_duplicates() {
+        let films = SyntheticCatalogue::new(500).with_cast(1).generate();
+
+        assert!(dedupe::find_duplicates(&films).is_empty());
+        assert_eq!(
+            SyntheticCatalogue::new(3).with_cast(0).generate()[0]
+                .stars
+                .len(),
+            1
+        );
+    }
+}
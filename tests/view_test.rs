@@ -0,0 +1,91 @@
+mod common;
+
+use common::*;
+use film_parser::view::*;
+use film_parser::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_films() -> Vec<Film> {
+        vec![
+            film("Raw")
+                .year(2016)
+                .director("Julia Ducournau")
+                .genre(&["Drama", "Horror"])
+                .build(),
+            film("Titane")
+                .year(2021)
+                .director("Julia Ducournau")
+                .genre(&["Drama", "Thriller"])
+                .build(),
+            film("Hereditary")
+                .year(2018)
+                .director("Ari Aster")
+                .genre(&["Horror"])
+                .build(),
+            film("Midsommar")
+                .year(2019)
+                .director("Ari Aster")
+                .genre(&["Horror", "Drama"])
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn test_sort_by_multiple_keys() {
+        let mut films = sample_films();
+        let keys = SortKey::parse_list("director,-year").expect("Failed to parse sort keys");
+        sort_films(&mut films, &keys);
+        let titles: Vec<&str> = films.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(titles, vec!["Midsommar", "Hereditary", "Titane", "Raw"]);
+    }
+
+    #[test]
+    fn test_invalid_sort_key() {
+        assert!(SortKey::parse_list("year,-rating").is_err());
+    }
+
+    #[test]
+    fn test_group_by_decade_and_genre() {
+        let films = sample_films();
+        let decades = group_films(&films, GroupBy::Decade);
+        let keys: Vec<&str> = decades.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["2010s", "2020s"]);
+        assert_eq!(decades[0].1.len(), 3);
+
+        let genres = group_films(&films, "genre".parse().expect("Failed to parse group"));
+        let drama = genres.iter().find(|(key, _)| key == "Drama").unwrap();
+        assert_eq!(drama.1.len(), 3);
+
+        let repeated = vec![film("Us").genre(&["Horror", "Horror"]).build()];
+        let genres = group_films(&repeated, GroupBy::Genre);
+        assert_eq!(genres.len(), 1);
+        assert_eq!(genres[0].1.len(), 1);
+    }
+
+    #[test]
+    fn test_years_and_decades_are_grouped_in_numeric_order() {
+        let mut films = sample_films();
+        films.push(film("Häxan").year(999).build());
+
+        let years = group_films(&films, GroupBy::Year);
+        let keys: Vec<&str> = years.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["999", "2016", "2018", "2019", "2021"]);
+
+        let decades = group_films(&films, GroupBy::Decade);
+        let keys: Vec<&str> = decades.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["990s", "2010s", "2020s"]);
+    }
+
+    #[test]
+    fn test_format_selected_fields() {
+        let fields = parse_field_list("year, title").expect("Failed to parse fields");
+        assert_eq!(
+            format_film_fields(&sample_films()[0], &fields),
+            "Year: 2016\nTitle: Raw\n"
+        );
+        assert!(parse_field_list("title,rating").is_err());
+    }
+}